@@ -0,0 +1,51 @@
+// Times parsing of a realistic 50-column CREATE TABLE statement. Uses a plain `main` rather than
+// a benchmarking framework so that it runs on stable without extra dependencies:
+//
+//     cargo bench --bench parse_schema
+
+extern crate nom_sql;
+
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 2_000;
+
+fn schema() -> String {
+    let types = [
+        "int(11) NOT NULL",
+        "varchar(255) DEFAULT NULL",
+        "bigint(20) unsigned NOT NULL",
+        "text",
+        "int(11) DEFAULT '0'",
+        "varchar(64) NOT NULL DEFAULT ''",
+        "datetime NOT NULL",
+        "tinyint(1) NOT NULL DEFAULT '0'",
+        "decimal(10,2) DEFAULT NULL",
+        "timestamp NULL DEFAULT NULL",
+    ];
+    let columns: Vec<String> = (0..50)
+        .map(|i| format!("  `col_{}` {}", i, types[i % types.len()]))
+        .collect();
+    format!(
+        "CREATE TABLE `wide` (\n{},\n  PRIMARY KEY (`col_0`)\n) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4",
+        columns.join(",\n")
+    )
+}
+
+fn main() {
+    let sql = schema();
+    assert!(nom_sql::parse_query(&sql).is_ok());
+
+    let mut best = Duration::from_secs(u64::MAX);
+    for _ in 0..5 {
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            nom_sql::parse_query(&sql).unwrap();
+        }
+        best = best.min(start.elapsed());
+    }
+    println!(
+        "parse 50-column CREATE TABLE: {:.1} us/iter (best of 5 x {})",
+        best.as_secs_f64() * 1e6 / f64::from(ITERATIONS),
+        ITERATIONS
+    );
+}
@@ -0,0 +1,433 @@
+use nom::character::complete::{multispace0, multispace1};
+use std::{fmt, str};
+
+use column::ColumnSpecification;
+use common::{qualified_table_name, sql_identifier, statement_terminator, ws_sep_comma, TableKey};
+use create::{column_specification, key_specification};
+use keywords::escape_if_keyword;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::combinator::{map, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{terminated, tuple};
+use nom::IResult;
+use table::Table;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum AlterOperation {
+    AddColumn(ColumnSpecification),
+    DropColumn(String),
+    RenameTable(Table),
+    /// Rename an index, as (old name, new name).
+    RenameIndex(String, String),
+    AddKey(TableKey),
+    DropPrimaryKey,
+    /// Drop a named index, written `DROP INDEX name` or `DROP KEY name`.
+    DropKey(String),
+    DropForeignKey(String),
+    /// MySQL's `MODIFY COLUMN`: redefine a column, keeping its name.
+    ModifyColumn(ColumnSpecification),
+    /// MySQL's `CHANGE COLUMN`: rename a column and redefine it, as (old name, new definition).
+    ChangeColumn(String, ColumnSpecification),
+}
+
+impl fmt::Display for AlterOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AlterOperation::AddColumn(ref spec) => write!(f, "ADD COLUMN {}", spec),
+            AlterOperation::DropColumn(ref name) => {
+                write!(f, "DROP COLUMN {}", escape_if_keyword(name))
+            }
+            AlterOperation::RenameTable(ref table) => write!(f, "RENAME TO {}", table),
+            AlterOperation::RenameIndex(ref old, ref new) => write!(
+                f,
+                "RENAME INDEX {} TO {}",
+                escape_if_keyword(old),
+                escape_if_keyword(new)
+            ),
+            AlterOperation::AddKey(ref key) => write!(f, "ADD {}", key),
+            AlterOperation::DropPrimaryKey => write!(f, "DROP PRIMARY KEY"),
+            AlterOperation::DropKey(ref name) => write!(f, "DROP KEY {}", escape_if_keyword(name)),
+            AlterOperation::DropForeignKey(ref name) => {
+                write!(f, "DROP FOREIGN KEY {}", escape_if_keyword(name))
+            }
+            AlterOperation::ModifyColumn(ref spec) => write!(f, "MODIFY COLUMN {}", spec),
+            AlterOperation::ChangeColumn(ref old, ref spec) => {
+                write!(f, "CHANGE COLUMN {} {}", escape_if_keyword(old), spec)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct AlterTableStatement {
+    pub table: Table,
+    pub operations: Vec<AlterOperation>,
+}
+
+impl fmt::Display for AlterTableStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ALTER TABLE {} ", self.table)?;
+        write!(
+            f,
+            "{}",
+            self.operations
+                .iter()
+                .map(|op| format!("{}", op))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+fn add_column(i: &[u8]) -> IResult<&[u8], AlterOperation> {
+    let (remaining_input, (_, _, _, spec)) = tuple((
+        tag_no_case("add"),
+        multispace1,
+        opt(terminated(tag_no_case("column"), multispace1)),
+        column_specification,
+    ))(i)?;
+
+    Ok((remaining_input, AlterOperation::AddColumn(spec)))
+}
+
+fn add_key(i: &[u8]) -> IResult<&[u8], AlterOperation> {
+    let (remaining_input, (_, _, key)) =
+        tuple((tag_no_case("add"), multispace1, key_specification))(i)?;
+
+    Ok((remaining_input, AlterOperation::AddKey(key)))
+}
+
+fn drop_key(i: &[u8]) -> IResult<&[u8], AlterOperation> {
+    let (remaining_input, (_, _, op)) = tuple((
+        tag_no_case("drop"),
+        multispace1,
+        alt((
+            map(
+                tuple((tag_no_case("primary"), multispace1, tag_no_case("key"))),
+                |_| AlterOperation::DropPrimaryKey,
+            ),
+            map(
+                tuple((
+                    tag_no_case("foreign"),
+                    multispace1,
+                    tag_no_case("key"),
+                    multispace1,
+                    sql_identifier,
+                )),
+                |t| AlterOperation::DropForeignKey(String::from(str::from_utf8(t.4).unwrap())),
+            ),
+            map(
+                tuple((
+                    alt((tag_no_case("index"), tag_no_case("key"))),
+                    multispace1,
+                    sql_identifier,
+                )),
+                |t| AlterOperation::DropKey(String::from(str::from_utf8(t.2).unwrap())),
+            ),
+        )),
+    ))(i)?;
+
+    Ok((remaining_input, op))
+}
+
+fn drop_column(i: &[u8]) -> IResult<&[u8], AlterOperation> {
+    let (remaining_input, (_, _, _, name)) = tuple((
+        tag_no_case("drop"),
+        multispace1,
+        opt(terminated(tag_no_case("column"), multispace1)),
+        sql_identifier,
+    ))(i)?;
+
+    let name = String::from(str::from_utf8(name).unwrap());
+    Ok((remaining_input, AlterOperation::DropColumn(name)))
+}
+
+fn modify_column(i: &[u8]) -> IResult<&[u8], AlterOperation> {
+    let (remaining_input, (_, _, _, spec)) = tuple((
+        tag_no_case("modify"),
+        multispace1,
+        opt(terminated(tag_no_case("column"), multispace1)),
+        column_specification,
+    ))(i)?;
+
+    Ok((remaining_input, AlterOperation::ModifyColumn(spec)))
+}
+
+fn change_column(i: &[u8]) -> IResult<&[u8], AlterOperation> {
+    let (remaining_input, (_, _, _, old, _, spec)) = tuple((
+        tag_no_case("change"),
+        multispace1,
+        opt(terminated(tag_no_case("column"), multispace1)),
+        sql_identifier,
+        multispace1,
+        column_specification,
+    ))(i)?;
+
+    let old = String::from(str::from_utf8(old).unwrap());
+    Ok((remaining_input, AlterOperation::ChangeColumn(old, spec)))
+}
+
+fn rename_table(i: &[u8]) -> IResult<&[u8], AlterOperation> {
+    let (remaining_input, (_, _, _, table)) = tuple((
+        tag_no_case("rename"),
+        multispace1,
+        opt(terminated(
+            alt((tag_no_case("to"), tag_no_case("as"))),
+            multispace1,
+        )),
+        qualified_table_name,
+    ))(i)?;
+
+    Ok((remaining_input, AlterOperation::RenameTable(table)))
+}
+
+fn rename_index(i: &[u8]) -> IResult<&[u8], AlterOperation> {
+    let (remaining_input, (_, _, _, _, old, _, _, _, new)) = tuple((
+        tag_no_case("rename"),
+        multispace1,
+        alt((tag_no_case("index"), tag_no_case("key"))),
+        multispace1,
+        sql_identifier,
+        multispace1,
+        tag_no_case("to"),
+        multispace1,
+        sql_identifier,
+    ))(i)?;
+
+    let old = String::from(str::from_utf8(old).unwrap());
+    let new = String::from(str::from_utf8(new).unwrap());
+    Ok((remaining_input, AlterOperation::RenameIndex(old, new)))
+}
+
+fn alter_operation(i: &[u8]) -> IResult<&[u8], AlterOperation> {
+    terminated(
+        alt((
+            add_key,
+            add_column,
+            drop_key,
+            drop_column,
+            modify_column,
+            change_column,
+            rename_index,
+            rename_table,
+        )),
+        multispace0,
+    )(i)
+}
+
+// Parse rule for a SQL ALTER TABLE statement; at least one operation is required.
+pub fn alter_table(i: &[u8]) -> IResult<&[u8], AlterTableStatement> {
+    let (remaining_input, (_, _, _, _, table, _, operations, _)) = tuple((
+        tag_no_case("alter"),
+        multispace1,
+        tag_no_case("table"),
+        multispace1,
+        qualified_table_name,
+        multispace1,
+        separated_list1(ws_sep_comma, alter_operation),
+        statement_terminator,
+    ))(i)?;
+
+    Ok((remaining_input, AlterTableStatement { table, operations }))
+}
+
+// Matches an ALTER TABLE statement that names a table but has no operations, which is invalid;
+// used to report a more helpful error than a generic parse failure.
+pub fn alter_table_without_operations(i: &[u8]) -> IResult<&[u8], ()> {
+    let (remaining_input, _) = tuple((
+        tag_no_case("alter"),
+        multispace1,
+        tag_no_case("table"),
+        multispace1,
+        qualified_table_name,
+        statement_terminator,
+    ))(i)?;
+
+    Ok((remaining_input, ()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use column::{Column, ColumnConstraint, IndexColumn};
+    use common::{ForeignKeyReference, SqlType};
+
+    #[test]
+    fn alter_table_operations() {
+        let qstring = "ALTER TABLE users ADD COLUMN age int(11), DROP name, RENAME TO people;";
+        let res = alter_table(qstring.as_bytes());
+        assert_eq!(
+            res.unwrap().1,
+            AlterTableStatement {
+                table: Table::from("users"),
+                operations: vec![
+                    AlterOperation::AddColumn(ColumnSpecification::new(
+                        Column::from("age"),
+                        SqlType::Int(Some(11)),
+                    )),
+                    AlterOperation::DropColumn(String::from("name")),
+                    AlterOperation::RenameTable(Table::from("people")),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn format_alter_table() {
+        let qstring = "alter table users add age int(11),drop column name";
+        let expected = "ALTER TABLE users ADD COLUMN age INT(11), DROP COLUMN name";
+        let res = alter_table(qstring.as_bytes());
+        assert_eq!(format!("{}", res.unwrap().1), expected);
+    }
+
+    #[test]
+    fn format_alter_table_qualified_names() {
+        for qstring in &[
+            "ALTER TABLE db1.users ADD COLUMN a INT",
+            "ALTER TABLE users RENAME TO archive.users",
+        ] {
+            let res = alter_table(qstring.as_bytes()).unwrap().1;
+            assert_eq!(format!("{}", res), *qstring);
+        }
+    }
+
+    #[test]
+    fn alter_table_rename_index() {
+        let qstring = "ALTER TABLE users RENAME INDEX email_idx TO users_email_idx;";
+        let res = alter_table(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.operations,
+            vec![AlterOperation::RenameIndex(
+                String::from("email_idx"),
+                String::from("users_email_idx"),
+            )]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "ALTER TABLE users RENAME INDEX email_idx TO users_email_idx"
+        );
+    }
+
+    #[test]
+    fn alter_table_add_key() {
+        let qstring = "ALTER TABLE users ADD UNIQUE KEY email_idx (email), ADD PRIMARY KEY (id);";
+        let res = alter_table(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.operations,
+            vec![
+                AlterOperation::AddKey(TableKey::UniqueKey {
+                    name: Some(String::from("email_idx")),
+                    columns: vec![IndexColumn::from("email")],
+                    comment: None,
+                    index_type: None
+                }),
+                AlterOperation::AddKey(TableKey::PrimaryKey {
+                    columns: vec![IndexColumn::from("id")],
+                    index_type: None
+                }),
+            ]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "ALTER TABLE users ADD UNIQUE KEY email_idx (email), ADD PRIMARY KEY (id)"
+        );
+    }
+
+    #[test]
+    fn alter_table_modify_column() {
+        let qstring = "ALTER TABLE users MODIFY COLUMN c BIGINT(20) NOT NULL;";
+        let res = alter_table(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.operations,
+            vec![AlterOperation::ModifyColumn(
+                ColumnSpecification::with_constraints(
+                    Column::from("c"),
+                    SqlType::Bigint(Some(20)),
+                    vec![ColumnConstraint::NotNull],
+                )
+            )]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "ALTER TABLE users MODIFY COLUMN c BIGINT(20) NOT NULL"
+        );
+    }
+
+    #[test]
+    fn alter_table_change_column() {
+        let qstring = "ALTER TABLE users CHANGE COLUMN old_name new_name INT, CHANGE a b text;";
+        let res = alter_table(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.operations,
+            vec![
+                AlterOperation::ChangeColumn(
+                    String::from("old_name"),
+                    ColumnSpecification::new(Column::from("new_name"), SqlType::Int(None)),
+                ),
+                AlterOperation::ChangeColumn(
+                    String::from("a"),
+                    ColumnSpecification::new(Column::from("b"), SqlType::Text),
+                ),
+            ]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "ALTER TABLE users CHANGE COLUMN old_name new_name INT, CHANGE COLUMN a b TEXT"
+        );
+    }
+
+    #[test]
+    fn alter_table_add_foreign_key() {
+        let qstring = "ALTER TABLE posts ADD CONSTRAINT fk_author \
+                       FOREIGN KEY (author_id) REFERENCES users (id);";
+        let res = alter_table(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.operations,
+            vec![AlterOperation::AddKey(TableKey::ForeignKey {
+                name: Some(String::from("fk_author")),
+                index_name: None,
+                columns: vec![Column::from("author_id")],
+                reference: ForeignKeyReference {
+                    table: Table::from("users"),
+                    columns: vec![Column::from("id")],
+                    on_delete: None,
+                    on_update: None,
+                }
+            })]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "ALTER TABLE posts ADD CONSTRAINT fk_author FOREIGN KEY (author_id) REFERENCES users (id)"
+        );
+    }
+
+    #[test]
+    fn alter_table_drop_keys() {
+        let qstring = "ALTER TABLE users DROP INDEX email_idx, DROP KEY name_idx, \
+                       DROP PRIMARY KEY, DROP FOREIGN KEY fk_team, DROP team_id;";
+        let res = alter_table(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.operations,
+            vec![
+                AlterOperation::DropKey(String::from("email_idx")),
+                AlterOperation::DropKey(String::from("name_idx")),
+                AlterOperation::DropPrimaryKey,
+                AlterOperation::DropForeignKey(String::from("fk_team")),
+                AlterOperation::DropColumn(String::from("team_id")),
+            ]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "ALTER TABLE users DROP KEY email_idx, DROP KEY name_idx, DROP PRIMARY KEY, \
+             DROP FOREIGN KEY fk_team, DROP COLUMN team_id"
+        );
+    }
+
+    #[test]
+    fn alter_table_requires_operation() {
+        let qstring = "ALTER TABLE users;";
+        assert!(alter_table(qstring.as_bytes()).is_err());
+        assert!(alter_table_without_operations(qstring.as_bytes()).is_ok());
+    }
+}
@@ -13,9 +13,10 @@ use nom::{
 };
 
 use crate::{
+    case::{case_expression_no_alias, CaseExpression},
     column::Column,
     common::{
-        as_alias, column_identifier_no_alias, integer_literal, type_identifier, Literal, SqlType,
+        as_alias, column_identifier_no_alias, numeric_literal, type_identifier, Literal, SqlType,
     },
 };
 
@@ -25,6 +26,7 @@ pub enum ArithmeticOperator {
     Subtract,
     Multiply,
     Divide,
+    Modulo,
 }
 
 #[derive(Debug, Clone, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -32,6 +34,7 @@ pub enum ArithmeticBase {
     Column(Column),
     Scalar(Literal),
     Bracketed(Box<Arithmetic>),
+    Case(Box<CaseExpression>),
 }
 
 #[derive(Debug, Clone, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -88,6 +91,7 @@ impl fmt::Display for ArithmeticOperator {
             ArithmeticOperator::Subtract => write!(f, "-"),
             ArithmeticOperator::Multiply => write!(f, "*"),
             ArithmeticOperator::Divide => write!(f, "/"),
+            ArithmeticOperator::Modulo => write!(f, "%"),
         }
     }
 }
@@ -96,8 +100,9 @@ impl fmt::Display for ArithmeticBase {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             ArithmeticBase::Column(ref col) => write!(f, "{}", col),
-            ArithmeticBase::Scalar(ref lit) => write!(f, "{}", lit.to_string()),
+            ArithmeticBase::Scalar(ref lit) => write!(f, "{}", lit),
             ArithmeticBase::Bracketed(ref ari) => write!(f, "({})", ari),
+            ArithmeticBase::Case(ref case) => write!(f, "{}", case),
         }
     }
 }
@@ -161,13 +166,17 @@ pub fn mul_div_operator(i: &[u8]) -> IResult<&[u8], ArithmeticOperator> {
     alt((
         map(tag("*"), |_| ArithmeticOperator::Multiply),
         map(tag("/"), |_| ArithmeticOperator::Divide),
+        map(tag("%"), |_| ArithmeticOperator::Modulo),
     ))(i)
 }
 
 // Base case for nested arithmetic expressions: column name or literal.
 pub fn arithmetic_base(i: &[u8]) -> IResult<&[u8], ArithmeticBase> {
     alt((
-        map(integer_literal, ArithmeticBase::Scalar),
+        map(numeric_literal, ArithmeticBase::Scalar),
+        map(case_expression_no_alias, |case| {
+            ArithmeticBase::Case(Box::new(case))
+        }),
         map(column_identifier_no_alias, ArithmeticBase::Column),
         map(
             delimited(
@@ -184,7 +193,8 @@ fn arithmetic(i: &[u8]) -> IResult<&[u8], Arithmetic> {
     let res = expr(i)?;
     match res.1 {
         ArithmeticItem::Base(ArithmeticBase::Column(_))
-        | ArithmeticItem::Base(ArithmeticBase::Scalar(_)) => {
+        | ArithmeticItem::Base(ArithmeticBase::Scalar(_))
+        | ArithmeticItem::Base(ArithmeticBase::Case(_)) => {
             Err(Error(nom::error::Error::new(i, nom::error::ErrorKind::Tag)))
         } // no operator
         ArithmeticItem::Base(ArithmeticBase::Bracketed(expr)) => Ok((res.0, *expr)),
@@ -243,7 +253,7 @@ pub fn arithmetic_expression(i: &[u8]) -> IResult<&[u8], ArithmeticExpression> {
 mod tests {
     use crate::arithmetic::{
         ArithmeticBase::Scalar,
-        ArithmeticOperator::{Add, Divide, Multiply, Subtract},
+        ArithmeticOperator::{Add, Divide, Modulo, Multiply, Subtract},
     };
 
     use super::*;
@@ -370,7 +380,7 @@ mod tests {
         let exprs = [
             "CAST(`t`.`foo` AS signed int) + CAST(`t`.`bar` AS signed int) ",
             "CAST(5 AS bigint) - foo ",
-            "CAST(5 AS bigint) - foo AS 5_minus_foo",
+            "CAST(5 AS bigint) - foo AS `5_minus_foo`",
         ];
 
         // XXX(malte): currently discards the cast and type information!
@@ -405,6 +415,7 @@ mod tests {
             "1 + 2 * 3",
             "2 * 3 - 1 / 3",
             "3 * (1 + 2)",
+            "1 + 7 % 3",
         ];
 
         let expects =
@@ -448,6 +459,15 @@ mod tests {
                         Arithmetic::new(Add, Scalar(1.into()), Scalar(2.into())),
                     ))),
                 },
+                Arithmetic {
+                    op: Add,
+                    left: ArithmeticItem::Base(Scalar(1.into())),
+                    right: ArithmeticItem::Expr(Box::new(Arithmetic::new(
+                        Modulo,
+                        Scalar(7.into()),
+                        Scalar(3.into()),
+                    ))),
+                },
             ];
 
         for (i, e) in qs.iter().enumerate() {
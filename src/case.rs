@@ -1,13 +1,15 @@
 use std::fmt;
 
 use column::Column;
-use common::{column_identifier_no_alias, literal, Literal};
+use common::{as_alias, column_identifier_no_alias, literal, Literal};
 use condition::{condition_expr, ConditionExpression};
 
+use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
-use nom::character::complete::multispace0;
-use nom::combinator::opt;
-use nom::sequence::{delimited, terminated, tuple};
+use nom::character::complete::{multispace0, multispace1};
+use nom::combinator::{map, opt};
+use nom::multi::many1;
+use nom::sequence::{delimited, pair, terminated, tuple};
 use nom::IResult;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
@@ -20,56 +22,158 @@ impl fmt::Display for ColumnOrLiteral {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             ColumnOrLiteral::Column(ref c) => write!(f, "{}", c)?,
-            ColumnOrLiteral::Literal(ref l) => write!(f, "{}", l.to_string())?,
+            ColumnOrLiteral::Literal(ref l) => write!(f, "{}", l)?,
         }
         Ok(())
     }
 }
 
+/// A `CASE ... END` expression, in either the searched form (`CASE WHEN a > 1 THEN ...`) or the
+/// simple form (`CASE x WHEN 1 THEN ...`). In the simple form `operand` is set and each branch's
+/// condition holds the value it is compared against.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
-pub struct CaseWhenExpression {
-    pub condition: ConditionExpression,
-    pub then_expr: ColumnOrLiteral,
+pub struct CaseExpression {
+    pub operand: Option<ColumnOrLiteral>,
+    pub branches: Vec<(ConditionExpression, ColumnOrLiteral)>,
     pub else_expr: Option<ColumnOrLiteral>,
+    pub alias: Option<String>,
 }
 
-impl fmt::Display for CaseWhenExpression {
+impl fmt::Display for CaseExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "CASE WHEN {} THEN {}", self.condition, self.then_expr)?;
+        write!(f, "CASE")?;
+        if let Some(ref operand) = self.operand {
+            write!(f, " {}", operand)?;
+        }
+        for (condition, value) in &self.branches {
+            write!(f, " WHEN {} THEN {}", condition, value)?;
+        }
         if let Some(ref expr) = self.else_expr {
             write!(f, " ELSE {}", expr)?;
         }
+        write!(f, " END")?;
+        if let Some(ref alias) = self.alias {
+            write!(f, " AS {}", alias)?;
+        }
         Ok(())
     }
 }
 
-pub fn case_when_column(i: &[u8]) -> IResult<&[u8], CaseWhenExpression> {
-    let (remaining_input, (_, _, condition, _, _, _, column, _, else_val, _)) = tuple((
-        tag_no_case("case when"),
-        multispace0,
+pub(crate) fn column_or_literal(i: &[u8]) -> IResult<&[u8], ColumnOrLiteral> {
+    alt((
+        map(literal, ColumnOrLiteral::Literal),
+        map(column_identifier_no_alias, ColumnOrLiteral::Column),
+    ))(i)
+}
+
+fn case_branch(i: &[u8]) -> IResult<&[u8], (ConditionExpression, ColumnOrLiteral)> {
+    let (remaining_input, (_, _, condition, _, _, _, value, _)) = tuple((
+        tag_no_case("when"),
+        multispace1,
         condition_expr,
         multispace0,
         tag_no_case("then"),
+        multispace1,
+        column_or_literal,
         multispace0,
-        column_identifier_no_alias,
-        multispace0,
+    ))(i)?;
+
+    Ok((remaining_input, (condition, value)))
+}
+
+// Parse rule for a CASE expression, in searched or simple form, without a trailing alias; for
+// use as an operand or function argument.
+pub fn case_expression_no_alias(i: &[u8]) -> IResult<&[u8], CaseExpression> {
+    let (remaining_input, (_, _, operand, branches, else_expr, _)) = tuple((
+        tag_no_case("case"),
+        multispace1,
+        opt(terminated(column_or_literal, multispace1)),
+        many1(case_branch),
         opt(delimited(
-            terminated(tag_no_case("else"), multispace0),
-            literal,
+            terminated(tag_no_case("else"), multispace1),
+            column_or_literal,
             multispace0,
         )),
         tag_no_case("end"),
     ))(i)?;
 
-    let then_expr = ColumnOrLiteral::Column(column);
-    let else_expr = else_val.map(|v| ColumnOrLiteral::Literal(v));
-
     Ok((
         remaining_input,
-        CaseWhenExpression {
-            condition,
-            then_expr,
+        CaseExpression {
+            operand,
+            branches,
             else_expr,
+            alias: None,
         },
     ))
 }
+
+// Parse rule for a CASE expression, in searched or simple form.
+pub fn case_expression(i: &[u8]) -> IResult<&[u8], CaseExpression> {
+    map(
+        pair(case_expression_no_alias, opt(as_alias)),
+        |(case, alias)| CaseExpression {
+            alias: alias.map(String::from),
+            ..case
+        },
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Operator;
+    use condition::ConditionBase;
+    use condition::ConditionExpression::*;
+    use condition::ConditionTree;
+
+    #[test]
+    fn searched_case() {
+        let qstring = "CASE WHEN a > 1 THEN 'hi' ELSE 'lo' END";
+        let res = case_expression(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res,
+            CaseExpression {
+                operand: None,
+                branches: vec![(
+                    ComparisonOp(ConditionTree {
+                        operator: Operator::Greater,
+                        left: Box::new(Base(ConditionBase::Field(Column::from("a")))),
+                        right: Box::new(Base(ConditionBase::Literal(Literal::Integer(1)))),
+                    }),
+                    ColumnOrLiteral::Literal(Literal::String(String::from("hi"))),
+                )],
+                else_expr: Some(ColumnOrLiteral::Literal(Literal::String(String::from(
+                    "lo"
+                )))),
+                alias: None,
+            }
+        );
+        assert_eq!(format!("{}", res), qstring);
+    }
+
+    #[test]
+    fn simple_case_without_else() {
+        let qstring = "CASE x WHEN 1 THEN 'one' WHEN 2 THEN y END AS label";
+        let res = case_expression(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res,
+            CaseExpression {
+                operand: Some(ColumnOrLiteral::Column(Column::from("x"))),
+                branches: vec![
+                    (
+                        Base(ConditionBase::Literal(Literal::Integer(1))),
+                        ColumnOrLiteral::Literal(Literal::String(String::from("one"))),
+                    ),
+                    (
+                        Base(ConditionBase::Literal(Literal::Integer(2))),
+                        ColumnOrLiteral::Column(Column::from("y")),
+                    ),
+                ],
+                else_expr: None,
+                alias: Some(String::from("label")),
+            }
+        );
+        assert_eq!(format!("{}", res), qstring);
+    }
+}
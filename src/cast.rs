@@ -0,0 +1,184 @@
+use std::fmt;
+
+use case::{column_or_literal, ColumnOrLiteral};
+use common::{as_alias, type_identifier, SqlType};
+
+use nom::branch::alt;
+use nom::bytes::complete::{tag, tag_no_case};
+use nom::character::complete::{multispace0, multispace1};
+use nom::combinator::{map, opt};
+use nom::sequence::{delimited, pair, preceded, separated_pair, tuple};
+use nom::IResult;
+
+/// A `CAST(expr AS type)` expression, also parsed from PostgreSQL's `expr::type` shorthand.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct CastExpression {
+    pub expr: ColumnOrLiteral,
+    pub target: CastTarget,
+    pub alias: Option<String>,
+}
+
+/// The type a `CAST` converts to.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum CastTarget {
+    /// MySQL's `SIGNED [INTEGER]`.
+    Signed,
+    /// MySQL's `UNSIGNED [INTEGER]`.
+    Unsigned,
+    /// MySQL's `CHAR` without a length.
+    Char,
+    Type(SqlType),
+}
+
+impl fmt::Display for CastTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CastTarget::Signed => write!(f, "SIGNED"),
+            CastTarget::Unsigned => write!(f, "UNSIGNED"),
+            CastTarget::Char => write!(f, "CHAR"),
+            CastTarget::Type(ref sql_type) => write!(f, "{}", sql_type),
+        }
+    }
+}
+
+impl fmt::Display for CastExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CAST({} AS {})", self.expr, self.target)?;
+        if let Some(ref alias) = self.alias {
+            write!(f, " AS {}", alias)?;
+        }
+        Ok(())
+    }
+}
+
+// Parse the target type of a cast, which besides the usual column types may be one of MySQL's
+// `SIGNED [INTEGER]`, `UNSIGNED [INTEGER]` or a `CHAR` without a length.
+fn cast_type(i: &[u8]) -> IResult<&[u8], CastTarget> {
+    let integer = || {
+        opt(preceded(
+            multispace1,
+            alt((tag_no_case("integer"), tag_no_case("int"))),
+        ))
+    };
+
+    let (remaining_input, target) = alt((
+        map(type_identifier, CastTarget::Type),
+        map(pair(tag_no_case("unsigned"), integer()), |_| {
+            CastTarget::Unsigned
+        }),
+        map(pair(tag_no_case("signed"), integer()), |_| {
+            CastTarget::Signed
+        }),
+        map(tag_no_case("char"), |_| CastTarget::Char),
+    ))(i)?;
+
+    // some column types consume the whitespace following them, which a trailing alias needs
+    let consumed = &i[..i.len() - remaining_input.len()];
+    let trailing = consumed
+        .iter()
+        .rev()
+        .take_while(|c| c.is_ascii_whitespace())
+        .count();
+    Ok((&i[consumed.len() - trailing..], target))
+}
+
+// Parse rule for a CAST expression or its `::` shorthand.
+pub fn cast_expression(i: &[u8]) -> IResult<&[u8], CastExpression> {
+    let cast_call = map(
+        tuple((
+            tag_no_case("cast"),
+            multispace0,
+            tag("("),
+            multispace0,
+            column_or_literal,
+            multispace1,
+            tag_no_case("as"),
+            multispace1,
+            cast_type,
+            multispace0,
+            tag(")"),
+        )),
+        |t| (t.4, t.8),
+    );
+    let shorthand = separated_pair(
+        column_or_literal,
+        delimited(multispace0, tag("::"), multispace0),
+        cast_type,
+    );
+
+    let (remaining_input, ((expr, target), alias)) =
+        pair(alt((cast_call, shorthand)), opt(as_alias))(i)?;
+
+    Ok((
+        remaining_input,
+        CastExpression {
+            expr,
+            target,
+            alias: alias.map(String::from),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use column::Column;
+    use common::Literal;
+
+    #[test]
+    fn cast_to_char() {
+        let res = cast_expression(b"CAST(id AS CHAR)").unwrap().1;
+        assert_eq!(
+            res,
+            CastExpression {
+                expr: ColumnOrLiteral::Column(Column::from("id")),
+                target: CastTarget::Char,
+                alias: None,
+            }
+        );
+        assert_eq!(format!("{}", res), "CAST(id AS CHAR)");
+
+        let res = cast_expression(b"cast(name as char(10)) AS short_name")
+            .unwrap()
+            .1;
+        assert_eq!(res.target, CastTarget::Type(SqlType::Char(10)));
+        assert_eq!(res.alias, Some(String::from("short_name")));
+    }
+
+    #[test]
+    fn numeric_casts() {
+        let res = cast_expression(b"CAST(x AS UNSIGNED)").unwrap().1;
+        assert_eq!(res.target, CastTarget::Unsigned);
+        assert_eq!(format!("{}", res), "CAST(x AS UNSIGNED)");
+
+        let res = cast_expression(b"CAST('42' AS SIGNED INTEGER)").unwrap().1;
+        assert_eq!(
+            res.expr,
+            ColumnOrLiteral::Literal(Literal::String(String::from("42")))
+        );
+        assert_eq!(res.target, CastTarget::Signed);
+        assert_eq!(format!("{}", res), "CAST('42' AS SIGNED)");
+
+        let res = cast_expression(b"CAST(x AS BIGINT)").unwrap().1;
+        assert_eq!(res.target, CastTarget::Type(SqlType::Bigint(None)));
+        assert_eq!(format!("{}", res), "CAST(x AS BIGINT)");
+    }
+
+    #[test]
+    fn postgres_shorthand() {
+        let res = cast_expression(b"price::double").unwrap().1;
+        assert_eq!(
+            res,
+            CastExpression {
+                expr: ColumnOrLiteral::Column(Column::from("price")),
+                target: CastTarget::Type(SqlType::Double),
+                alias: None,
+            }
+        );
+        assert_eq!(format!("{}", res), "CAST(price AS DOUBLE)");
+
+        let res = cast_expression(b"x::text").unwrap().1;
+        assert_eq!(res.target, CastTarget::Type(SqlType::Text));
+        assert_eq!(format!("{}", res), "CAST(x AS TEXT)");
+    }
+}
@@ -2,9 +2,11 @@ use std::cmp::Ordering;
 use std::fmt::{self, Display};
 use std::str;
 
-use case::CaseWhenExpression;
-use common::{Literal, SqlType};
-use keywords::escape_if_keyword;
+use case::CaseExpression;
+use common::{ForeignKeyReference, Literal, SqlType};
+use keywords::{escape_if_keyword, sql_keyword};
+use order::OrderType;
+use table::opt_eq_ignore_case;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum FunctionExpression {
@@ -68,13 +70,15 @@ impl<'a> From<Vec<FunctionArgument>> for FunctionArguments {
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum FunctionArgument {
     Column(Column),
-    Conditional(CaseWhenExpression),
+    Literal(Literal),
+    Conditional(CaseExpression),
 }
 
 impl Display for FunctionArgument {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             FunctionArgument::Column(ref col) => write!(f, "{}", col)?,
+            FunctionArgument::Literal(ref lit) => write!(f, "{}", lit)?,
             FunctionArgument::Conditional(ref e) => {
                 write!(f, "{}", e)?;
             }
@@ -112,9 +116,35 @@ impl fmt::Display for Column {
     }
 }
 
+impl Column {
+    /// Compares two columns ignoring ASCII case in their name, alias and table, as SQL
+    /// identifiers usually are. Function expressions are compared exactly. The derived
+    /// `PartialEq` remains case-sensitive.
+    pub fn eq_ignore_case(&self, other: &Column) -> bool {
+        self.name.eq_ignore_ascii_case(&other.name)
+            && opt_eq_ignore_case(&self.alias, &other.alias)
+            && opt_eq_ignore_case(&self.table, &other.table)
+            && self.function == other.function
+    }
+
+    /// Renders the column as `table.column`, or just `column` if it has no table, quoting
+    /// either part if it is a keyword. Unlike `Display`, the alias is left out.
+    pub fn qualified_name(&self) -> String {
+        match self.table {
+            Some(ref table) => format!(
+                "{}.{}",
+                escape_if_keyword(table),
+                escape_if_keyword(&self.name)
+            ),
+            None => escape_if_keyword(&self.name),
+        }
+    }
+}
+
+/// Splits a dotted `table.name` into its parts; the name is always the last segment.
 impl<'a> From<&'a str> for Column {
     fn from(c: &str) -> Column {
-        match c.find(".") {
+        match c.rfind('.') {
             None => Column {
                 name: String::from(c),
                 alias: None,
@@ -165,9 +195,19 @@ pub enum ColumnConstraint {
     CharacterSet(String),
     Collation(String),
     DefaultValue(Literal),
+    DefaultExpression(String),
+    /// A generated column's expression (raw text), and whether it is STORED rather than VIRTUAL.
+    Generated(String, bool),
     AutoIncrement,
+    /// SQLite's `AUTOINCREMENT`, which only applies to an `INTEGER PRIMARY KEY` column and keeps
+    /// row ids from being reused.
+    Autoincrement,
     PrimaryKey,
     Unique,
+    /// An inline foreign key, `REFERENCES table [(columns)] [ON DELETE ...] [ON UPDATE ...]`.
+    References(ForeignKeyReference),
+    /// MySQL's `SRID n` on a spatial column, the spatial reference system its values use.
+    Srid(u32),
 }
 
 impl fmt::Display for ColumnConstraint {
@@ -176,12 +216,20 @@ impl fmt::Display for ColumnConstraint {
             ColumnConstraint::NotNull => write!(f, "NOT NULL"),
             ColumnConstraint::CharacterSet(ref charset) => write!(f, "CHARACTER SET {}", charset),
             ColumnConstraint::Collation(ref collation) => write!(f, "COLLATE {}", collation),
-            ColumnConstraint::DefaultValue(ref literal) => {
-                write!(f, "DEFAULT {}", literal.to_string())
-            }
+            ColumnConstraint::DefaultValue(ref literal) => write!(f, "DEFAULT {}", literal),
+            ColumnConstraint::DefaultExpression(ref expr) => write!(f, "DEFAULT ({})", expr),
+            ColumnConstraint::Generated(ref expr, stored) => write!(
+                f,
+                "GENERATED ALWAYS AS ({}) {}",
+                expr,
+                if stored { "STORED" } else { "VIRTUAL" }
+            ),
             ColumnConstraint::AutoIncrement => write!(f, "AUTO_INCREMENT"),
+            ColumnConstraint::Autoincrement => write!(f, "AUTOINCREMENT"),
             ColumnConstraint::PrimaryKey => write!(f, "PRIMARY KEY"),
             ColumnConstraint::Unique => write!(f, "UNIQUE"),
+            ColumnConstraint::References(ref reference) => write!(f, "{}", reference),
+            ColumnConstraint::Srid(srid) => write!(f, "SRID {}", srid),
         }
     }
 }
@@ -206,7 +254,7 @@ impl fmt::Display for ColumnSpecification {
             write!(f, " {}", constraint)?;
         }
         if let Some(ref comment) = self.comment {
-            write!(f, " COMMENT '{}'", comment)?;
+            write!(f, " COMMENT {}", Literal::from(comment.as_str()))?;
         }
         Ok(())
     }
@@ -234,12 +282,166 @@ impl ColumnSpecification {
             comment: None,
         }
     }
+
+    /// Checks that the column's constraints are consistent with its type. Currently this only
+    /// rejects a negative default on an unsigned integer column.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let unsigned = matches!(
+            self.sql_type,
+            SqlType::UnsignedTinyint(_) | SqlType::UnsignedInt(_) | SqlType::UnsignedBigint(_)
+        );
+        let negative_default = self.constraints.iter().any(|c| match *c {
+            ColumnConstraint::DefaultValue(Literal::Integer(i)) => i < 0,
+            _ => false,
+        });
+        if unsigned && negative_default {
+            return Err("negative default on unsigned column");
+        }
+        Ok(())
+    }
+
+    /// Returns the generation expression if this is a generated column.
+    pub fn generation_expression(&self) -> Option<&str> {
+        self.constraints.iter().find_map(|c| match *c {
+            ColumnConstraint::Generated(ref expr, _) => Some(expr.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Names of the columns referenced by this column's generation expression, in order of first
+    /// appearance. The expression is only scanned for identifiers, skipping string literals,
+    /// numbers, keywords and function names; table qualifiers are dropped.
+    pub fn generation_dependencies(&self) -> Vec<String> {
+        let expr = match self.generation_expression() {
+            Some(expr) => expr.as_bytes(),
+            None => return vec![],
+        };
+        let is_ident_char = |c: u8| c.is_ascii_alphanumeric() || c == b'_' || c == b'$';
+
+        let mut deps: Vec<String> = Vec::new();
+        let mut i = 0;
+        while i < expr.len() {
+            let c = expr[i];
+            let (ident, end) = if c == b'\'' || c == b'"' {
+                // skip string literal
+                let mut j = i + 1;
+                while j < expr.len() && expr[j] != c {
+                    j += if expr[j] == b'\\' { 2 } else { 1 };
+                }
+                (None, j + 1)
+            } else if c == b'`' {
+                let end = expr[i + 1..]
+                    .iter()
+                    .position(|&b| b == b'`')
+                    .map_or(expr.len(), |p| i + 1 + p);
+                (Some(&expr[i + 1..end]), end + 1)
+            } else if is_ident_char(c) {
+                let end = expr[i..]
+                    .iter()
+                    .position(|&b| !is_ident_char(b))
+                    .map_or(expr.len(), |p| i + p);
+                let word = &expr[i..end];
+                if c.is_ascii_digit() || sql_keyword(word).is_ok() {
+                    (None, end)
+                } else {
+                    (Some(word), end)
+                }
+            } else {
+                (None, i + 1)
+            };
+            i = end;
+
+            let ident = match ident {
+                Some(ident) => ident,
+                None => continue,
+            };
+            let rest = expr.get(i..).unwrap_or(&[]);
+            let next = rest.iter().find(|c| !c.is_ascii_whitespace());
+            // function names and table qualifiers are not column references
+            if next == Some(&b'(') || next == Some(&b'.') {
+                continue;
+            }
+            let name = String::from_utf8_lossy(ident).into_owned();
+            if !deps.contains(&name) {
+                deps.push(name);
+            }
+        }
+        deps
+    }
+}
+
+/// A column of an index or key, with the prefix length, collation and sort order it may be given
+/// there, as in `name(10) COLLATE utf8mb4_bin DESC`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct IndexColumn {
+    pub column: Column,
+    /// The number of leading characters (or bytes) of the column that are indexed.
+    pub length: Option<u16>,
+    pub collation: Option<String>,
+    pub order: Option<OrderType>,
+}
+
+impl fmt::Display for IndexColumn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", escape_if_keyword(&self.column.name))?;
+        if let Some(length) = self.length {
+            write!(f, "({})", length)?;
+        }
+        if let Some(ref collation) = self.collation {
+            write!(f, " COLLATE {}", collation)?;
+        }
+        if let Some(ref order) = self.order {
+            write!(f, " {}", order)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Column> for IndexColumn {
+    fn from(column: Column) -> IndexColumn {
+        IndexColumn {
+            column,
+            length: None,
+            collation: None,
+            order: None,
+        }
+    }
+}
+
+impl From<&str> for IndexColumn {
+    fn from(c: &str) -> IndexColumn {
+        IndexColumn::from(Column::from(c))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn column_eq_ignore_case() {
+        let lower = Column::from("users.email");
+        let upper = Column::from("Users.EMAIL");
+        assert_ne!(lower, upper);
+        assert!(lower.eq_ignore_case(&upper));
+
+        assert!(!lower.eq_ignore_case(&Column::from("email")));
+        assert!(!lower.eq_ignore_case(&Column::from("users.mail")));
+    }
+
+    #[test]
+    fn column_qualified_name() {
+        assert_eq!(Column::from("users.email").qualified_name(), "users.email");
+        assert_eq!(Column::from("email").qualified_name(), "email");
+        assert_eq!(Column::from("order.key").qualified_name(), "`order`.`key`");
+
+        let aliased = Column {
+            alias: Some(String::from("e")),
+            ..Column::from("users.email")
+        };
+        assert_eq!(aliased.qualified_name(), "users.email");
+    }
+
     #[test]
     fn column_from_str() {
         let s = "table.col";
@@ -254,6 +456,15 @@ mod tests {
                 function: None,
             }
         );
+
+        let c = Column::from("col");
+        assert_eq!((c.name.as_str(), c.table), ("col", None));
+
+        let c = Column::from("db.table.col");
+        assert_eq!(
+            (c.name.as_str(), c.table),
+            ("col", Some(String::from("db.table")))
+        );
     }
 
     #[test]
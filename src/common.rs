@@ -1,17 +1,21 @@
 use nom::branch::alt;
 use nom::character::complete::{alphanumeric1, digit1, line_ending, multispace0, multispace1};
 use nom::character::is_alphanumeric;
-use nom::combinator::{map, not, peek};
+use nom::combinator::{map, map_opt, not, peek, recognize, verify};
 use nom::{IResult, InputLength, Parser};
 use std::fmt::{self, Display};
+use std::hash::{Hash, Hasher};
+use std::mem;
 use std::str;
 use std::str::FromStr;
 
 use arithmetic::{arithmetic_expression, ArithmeticExpression};
-use case::case_when_column;
-use column::{Column, FunctionArgument, FunctionArguments, FunctionExpression};
+use case::{case_expression, case_expression_no_alias, CaseExpression};
+use cast::{cast_expression, CastExpression};
+use column::{Column, FunctionArgument, FunctionArguments, FunctionExpression, IndexColumn};
+use dialect::{self, dialect_allows, Dialect};
 use keywords::{escape_if_keyword, sql_keyword};
-use nom::bytes::complete::{is_not, tag, tag_no_case, take, take_until, take_while1};
+use nom::bytes::complete::{is_not, tag, tag_no_case, take, take_while, take_while1};
 use nom::combinator::opt;
 use nom::error::{ErrorKind, ParseError};
 use nom::multi::{fold_many0, many0, many1, separated_list0};
@@ -23,12 +27,13 @@ pub enum SqlType {
     Bool,
     Char(u16),
     Varchar(u16),
-    Int(u16),
-    UnsignedInt(u16),
-    Bigint(u16),
-    UnsignedBigint(u16),
-    Tinyint(u16),
-    UnsignedTinyint(u16),
+    /// Integer types carry their display width, if one was written.
+    Int(Option<u16>),
+    UnsignedInt(Option<u16>),
+    Bigint(Option<u16>),
+    UnsignedBigint(Option<u16>),
+    Tinyint(Option<u16>),
+    UnsignedTinyint(Option<u16>),
     Blob,
     Longblob,
     Mediumblob,
@@ -46,7 +51,34 @@ pub enum SqlType {
     Binary(u16),
     Varbinary(u16),
     Enum(Vec<Literal>),
+    Set(Vec<Literal>),
     Decimal(u8, u8),
+    Json,
+    Geometry,
+    Point,
+    Linestring,
+    Polygon,
+    Multipoint,
+    Multilinestring,
+    Multipolygon,
+    Geometrycollection,
+}
+
+// Writes an integer type name, with its display width if one was written.
+fn write_integer_type(
+    f: &mut fmt::Formatter,
+    name: &str,
+    width: Option<u16>,
+    unsigned: bool,
+) -> fmt::Result {
+    write!(f, "{}", name)?;
+    if let Some(width) = width {
+        write!(f, "({})", width)?;
+    }
+    if unsigned {
+        write!(f, " UNSIGNED")?;
+    }
+    Ok(())
 }
 
 impl fmt::Display for SqlType {
@@ -55,12 +87,12 @@ impl fmt::Display for SqlType {
             SqlType::Bool => write!(f, "BOOL"),
             SqlType::Char(len) => write!(f, "CHAR({})", len),
             SqlType::Varchar(len) => write!(f, "VARCHAR({})", len),
-            SqlType::Int(len) => write!(f, "INT({})", len),
-            SqlType::UnsignedInt(len) => write!(f, "INT({}) UNSIGNED", len),
-            SqlType::Bigint(len) => write!(f, "BIGINT({})", len),
-            SqlType::UnsignedBigint(len) => write!(f, "BIGINT({}) UNSIGNED", len),
-            SqlType::Tinyint(len) => write!(f, "TINYINT({})", len),
-            SqlType::UnsignedTinyint(len) => write!(f, "TINYINT({}) UNSIGNED", len),
+            SqlType::Int(len) => write_integer_type(f, "INT", len, false),
+            SqlType::UnsignedInt(len) => write_integer_type(f, "INT", len, true),
+            SqlType::Bigint(len) => write_integer_type(f, "BIGINT", len, false),
+            SqlType::UnsignedBigint(len) => write_integer_type(f, "BIGINT", len, true),
+            SqlType::Tinyint(len) => write_integer_type(f, "TINYINT", len, false),
+            SqlType::UnsignedTinyint(len) => write_integer_type(f, "TINYINT", len, true),
             SqlType::Blob => write!(f, "BLOB"),
             SqlType::Longblob => write!(f, "LONGBLOB"),
             SqlType::Mediumblob => write!(f, "MEDIUMBLOB"),
@@ -77,12 +109,236 @@ impl fmt::Display for SqlType {
             SqlType::Timestamp => write!(f, "TIMESTAMP"),
             SqlType::Binary(len) => write!(f, "BINARY({})", len),
             SqlType::Varbinary(len) => write!(f, "VARBINARY({})", len),
-            SqlType::Enum(_) => write!(f, "ENUM(...)"),
+            SqlType::Enum(ref members) => write!(f, "ENUM({})", member_list_display(members)),
+            SqlType::Set(ref members) => write!(f, "SET({})", member_list_display(members)),
             SqlType::Decimal(m, d) => write!(f, "DECIMAL({}, {})", m, d),
+            SqlType::Json => write!(f, "JSON"),
+            SqlType::Geometry => write!(f, "GEOMETRY"),
+            SqlType::Point => write!(f, "POINT"),
+            SqlType::Linestring => write!(f, "LINESTRING"),
+            SqlType::Polygon => write!(f, "POLYGON"),
+            SqlType::Multipoint => write!(f, "MULTIPOINT"),
+            SqlType::Multilinestring => write!(f, "MULTILINESTRING"),
+            SqlType::Multipolygon => write!(f, "MULTIPOLYGON"),
+            SqlType::Geometrycollection => write!(f, "GEOMETRYCOLLECTION"),
         }
     }
 }
 
+fn member_list_display(members: &[Literal]) -> String {
+    members
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl SqlType {
+    /// All variants that carry no parameters, in declaration order.
+    ///
+    /// The remaining variants are parameterized and so cannot be enumerated: `Char`, `Varchar`,
+    /// `Binary` and `Varbinary` (length); `Int`, `UnsignedInt`, `Bigint`, `UnsignedBigint`,
+    /// `Tinyint` and `UnsignedTinyint` (optional display width); `DateTime` (fractional seconds
+    /// precision); `Decimal` (precision and scale); and `Enum` and `Set` (members).
+    ///
+    /// This list is maintained by hand; `all_simple_variants_parse` checks that it stays in sync.
+    pub fn all_simple_variants() -> Vec<SqlType> {
+        vec![
+            SqlType::Bool,
+            SqlType::Blob,
+            SqlType::Longblob,
+            SqlType::Mediumblob,
+            SqlType::Tinyblob,
+            SqlType::Double,
+            SqlType::Float,
+            SqlType::Real,
+            SqlType::Tinytext,
+            SqlType::Mediumtext,
+            SqlType::Longtext,
+            SqlType::Text,
+            SqlType::Date,
+            SqlType::Timestamp,
+            SqlType::Json,
+            SqlType::Geometry,
+            SqlType::Point,
+            SqlType::Linestring,
+            SqlType::Polygon,
+            SqlType::Multipoint,
+            SqlType::Multilinestring,
+            SqlType::Multipolygon,
+            SqlType::Geometrycollection,
+        ]
+    }
+
+    /// Returns true if changing a column from this type to `other` can never lose or alter stored
+    /// values, e.g. `INT` to `BIGINT` or `VARCHAR(100)` to `VARCHAR(200)`. A type is trivially a
+    /// widening of itself. Integer display widths are ignored, as they do not affect the range.
+    pub fn is_widening_to(&self, other: &SqlType) -> bool {
+        use self::SqlType::*;
+
+        if self == other {
+            return true;
+        }
+        if let (Some((from_signed, from_bytes)), Some((to_signed, to_bytes))) =
+            (self.integer_range(), other.integer_range())
+        {
+            return match (from_signed, to_signed) {
+                (false, true) => to_bytes > from_bytes,
+                (true, false) => false,
+                _ => to_bytes >= from_bytes,
+            };
+        }
+        // CHAR pads with spaces and BINARY with zero bytes, so only CHAR can widen into CHAR
+        if let (Some(from), Some(to)) = (self.text_capacity(), other.text_capacity()) {
+            return match (self, other) {
+                (&Char(_), &Char(_)) => to >= from,
+                (_, &Char(_)) => false,
+                _ => to >= from,
+            };
+        }
+        if let (Some(from), Some(to)) = (self.binary_capacity(), other.binary_capacity()) {
+            return match *other {
+                Binary(_) => false,
+                _ => to >= from,
+            };
+        }
+
+        match (self, other) {
+            (_, &Decimal(m, d)) if self.integer_range().is_some() => {
+                let digits = match self.integer_range() {
+                    Some((_, 1)) => 3,
+                    Some((_, 4)) => 10,
+                    Some((true, _)) => 19,
+                    Some((false, _)) => 20,
+                    None => unreachable!(),
+                };
+                m.saturating_sub(d) >= digits
+            }
+            // a double's 53-bit mantissa holds any 32-bit integer, a float's 24 bits only 8-bit ones
+            (_, &Double) | (_, &Real) if self.integer_range().is_some() => {
+                matches!(self.integer_range(), Some((_, 1)) | Some((_, 4)))
+            }
+            (_, &Float) => matches!(self.integer_range(), Some((_, 1))),
+            (&Float, &Double) | (&Float, &Real) | (&Double, &Real) | (&Real, &Double) => true,
+            (&Decimal(m, d), &Decimal(m2, d2)) => {
+                d2 >= d && m2.saturating_sub(d2) >= m.saturating_sub(d)
+            }
+            (&Date, &DateTime(_)) | (&Timestamp, &DateTime(_)) => true,
+            (&DateTime(p), &DateTime(q)) => q >= p,
+            (&Enum(ref a), &Enum(ref b)) | (&Set(ref a), &Set(ref b)) => b.starts_with(a),
+            (&Point, &Geometry)
+            | (&Linestring, &Geometry)
+            | (&Polygon, &Geometry)
+            | (&Multipoint, &Geometry)
+            | (&Multilinestring, &Geometry)
+            | (&Multipolygon, &Geometry)
+            | (&Geometrycollection, &Geometry) => true,
+            (&Multipoint, &Geometrycollection)
+            | (&Multilinestring, &Geometrycollection)
+            | (&Multipolygon, &Geometrycollection) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the 1-based ordinal MySQL stores for `member` in an `ENUM` column. Members are
+    /// compared case-insensitively, as under MySQL's default collations. Returns `None` if this
+    /// is not an `ENUM` or `member` is not one of its values; MySQL stores the error value 0 then.
+    pub fn enum_index(&self, member: &str) -> Option<usize> {
+        match *self {
+            SqlType::Enum(ref members) => members
+                .iter()
+                .position(|m| match *m {
+                    Literal::String(ref s) => s.eq_ignore_ascii_case(member),
+                    _ => false,
+                })
+                .map(|p| p + 1),
+            _ => None,
+        }
+    }
+
+    /// The family this type belongs to, for grouping related types, e.g. in schema diffs.
+    pub fn family(&self) -> SqlTypeFamily {
+        use self::SqlType::*;
+
+        match *self {
+            Bool => SqlTypeFamily::Boolean,
+            Int(_) | UnsignedInt(_) | Bigint(_) | UnsignedBigint(_) | Tinyint(_)
+            | UnsignedTinyint(_) | Double | Float | Real | Decimal(..) => SqlTypeFamily::Numeric,
+            Char(_) | Varchar(_) | Tinytext | Mediumtext | Longtext | Text | Enum(_) | Set(_) => {
+                SqlTypeFamily::String
+            }
+            Blob | Longblob | Mediumblob | Tinyblob | Binary(_) | Varbinary(_) => {
+                SqlTypeFamily::Binary
+            }
+            Date | DateTime(_) | Timestamp => SqlTypeFamily::Temporal,
+            Json => SqlTypeFamily::Json,
+            Geometry | Point | Linestring | Polygon | Multipoint | Multilinestring
+            | Multipolygon | Geometrycollection => SqlTypeFamily::Spatial,
+        }
+    }
+
+    /// The maximum length of values of a character or binary string type: the declared length of
+    /// `CHAR`, `VARCHAR`, `BINARY` and `VARBINARY`, and the fixed capacity of the `TEXT` and `BLOB`
+    /// types. Returns `None` for all other types.
+    pub fn max_length(&self) -> Option<u32> {
+        self.text_capacity()
+            .or_else(|| self.binary_capacity())
+            .map(|len| len as u32)
+    }
+
+    // Signedness and storage size in bytes of integer types (treating BOOL as TINYINT).
+    fn integer_range(&self) -> Option<(bool, u8)> {
+        match *self {
+            SqlType::Bool | SqlType::Tinyint(_) => Some((true, 1)),
+            SqlType::UnsignedTinyint(_) => Some((false, 1)),
+            SqlType::Int(_) => Some((true, 4)),
+            SqlType::UnsignedInt(_) => Some((false, 4)),
+            SqlType::Bigint(_) => Some((true, 8)),
+            SqlType::UnsignedBigint(_) => Some((false, 8)),
+            _ => None,
+        }
+    }
+
+    // Maximum length of character string types.
+    fn text_capacity(&self) -> Option<u64> {
+        match *self {
+            SqlType::Char(len) | SqlType::Varchar(len) => Some(u64::from(len)),
+            SqlType::Tinytext => Some(255),
+            SqlType::Text => Some(65_535),
+            SqlType::Mediumtext => Some(16_777_215),
+            SqlType::Longtext => Some(4_294_967_295),
+            _ => None,
+        }
+    }
+
+    // Maximum length of binary string types.
+    fn binary_capacity(&self) -> Option<u64> {
+        match *self {
+            SqlType::Binary(len) | SqlType::Varbinary(len) => Some(u64::from(len)),
+            SqlType::Tinyblob => Some(255),
+            SqlType::Blob => Some(65_535),
+            SqlType::Mediumblob => Some(16_777_215),
+            SqlType::Longblob => Some(4_294_967_295),
+            _ => None,
+        }
+    }
+}
+
+/// A group of related SQL types, as returned by `SqlType::family`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum SqlTypeFamily {
+    Boolean,
+    /// Integer, floating point and fixed point types.
+    Numeric,
+    /// Character strings, including `ENUM` and `SET`.
+    String,
+    Binary,
+    /// Dates, times and timestamps.
+    Temporal,
+    Json,
+    Spatial,
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct Real {
     pub integral: i32,
@@ -94,6 +350,7 @@ pub enum ItemPlaceholder {
     QuestionMark,
     DollarNumber(i32),
     ColonNumber(i32),
+    ColonName(String),
 }
 
 impl ToString for ItemPlaceholder {
@@ -102,22 +359,74 @@ impl ToString for ItemPlaceholder {
             ItemPlaceholder::QuestionMark => "?".to_string(),
             ItemPlaceholder::DollarNumber(ref i) => format!("${}", i),
             ItemPlaceholder::ColonNumber(ref i) => format!(":{}", i),
+            ItemPlaceholder::ColonName(ref name) => format!(":{}", name),
         }
     }
 }
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Literal {
     Null,
     Integer(i64),
     UnsignedInteger(u64),
     FixedPoint(Real),
+    /// A number in scientific notation, or a decimal that `FixedPoint` cannot represent exactly.
+    Float(f64),
     String(String),
     Blob(Vec<u8>),
+    /// A bit-value literal such as `b'1010'`, keeping its leading zeros.
+    BitString(Vec<bool>),
     CurrentTime,
     CurrentDate,
     CurrentTimestamp,
     Placeholder(ItemPlaceholder),
+    /// The `DEFAULT` keyword in an `INSERT` row, standing for the column's default value.
+    Default,
+}
+
+// Floats compare and hash by their bit pattern, so that literals can be `Eq` and `Hash`.
+impl PartialEq for Literal {
+    fn eq(&self, other: &Literal) -> bool {
+        match (self, other) {
+            (Literal::Null, Literal::Null) => true,
+            (Literal::Integer(a), Literal::Integer(b)) => a == b,
+            (Literal::UnsignedInteger(a), Literal::UnsignedInteger(b)) => a == b,
+            (Literal::FixedPoint(a), Literal::FixedPoint(b)) => a == b,
+            (Literal::Float(a), Literal::Float(b)) => a.to_bits() == b.to_bits(),
+            (Literal::String(a), Literal::String(b)) => a == b,
+            (Literal::Blob(a), Literal::Blob(b)) => a == b,
+            (Literal::BitString(a), Literal::BitString(b)) => a == b,
+            (Literal::CurrentTime, Literal::CurrentTime) => true,
+            (Literal::CurrentDate, Literal::CurrentDate) => true,
+            (Literal::CurrentTimestamp, Literal::CurrentTimestamp) => true,
+            (Literal::Placeholder(a), Literal::Placeholder(b)) => a == b,
+            (Literal::Default, Literal::Default) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Literal {}
+
+impl Hash for Literal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match *self {
+            Literal::Integer(i) => i.hash(state),
+            Literal::UnsignedInteger(i) => i.hash(state),
+            Literal::FixedPoint(ref f) => f.hash(state),
+            Literal::Float(f) => f.to_bits().hash(state),
+            Literal::String(ref s) => s.hash(state),
+            Literal::Blob(ref b) => b.hash(state),
+            Literal::BitString(ref b) => b.hash(state),
+            Literal::Placeholder(ref p) => p.hash(state),
+            Literal::Null
+            | Literal::CurrentTime
+            | Literal::CurrentDate
+            | Literal::CurrentTimestamp
+            | Literal::Default => (),
+        }
+    }
 }
 
 impl From<i64> for Literal {
@@ -156,25 +465,48 @@ impl<'a> From<&'a str> for Literal {
     }
 }
 
-impl ToString for Literal {
-    fn to_string(&self) -> String {
+// Every literal is displayed in a form that parses back to the same literal.
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Literal::Null => "NULL".to_string(),
-            Literal::Integer(ref i) => format!("{}", i),
-            Literal::UnsignedInteger(ref i) => format!("{}", i),
-            Literal::FixedPoint(ref f) => format!("{}.{}", f.integral, f.fractional),
-            Literal::String(ref s) => format!("'{}'", s.replace('\'', "''")),
-            Literal::Blob(ref bv) => format!(
-                "{}",
-                bv.iter()
-                    .map(|v| format!("{:x}", v))
-                    .collect::<Vec<String>>()
-                    .join(" ")
-            ),
-            Literal::CurrentTime => "CURRENT_TIME".to_string(),
-            Literal::CurrentDate => "CURRENT_DATE".to_string(),
-            Literal::CurrentTimestamp => "CURRENT_TIMESTAMP".to_string(),
-            Literal::Placeholder(ref item) => item.to_string(),
+            Literal::Null => write!(f, "NULL"),
+            Literal::Integer(i) => write!(f, "{}", i),
+            Literal::UnsignedInteger(i) => write!(f, "{}", i),
+            Literal::FixedPoint(ref r) => write!(f, "{}.{}", r.integral, r.fractional),
+            Literal::Float(n) => {
+                // plain decimal notation where it is shorter and still parses back to a float
+                let plain = format!("{}", n);
+                let scientific = format!("{:e}", n);
+                let round_trips =
+                    numeric_literal(plain.as_bytes()) == Ok((&b""[..], Literal::Float(n)));
+                if round_trips && plain.len() < scientific.len() {
+                    write!(f, "{}", plain)
+                } else {
+                    write!(f, "{}", scientific)
+                }
+            }
+            Literal::String(ref s) => {
+                write!(f, "'{}'", s.replace('\\', "\\\\").replace('\'', "''"))
+            }
+            Literal::Blob(ref bv) => {
+                write!(f, "X'")?;
+                for byte in bv {
+                    write!(f, "{:02x}", byte)?;
+                }
+                write!(f, "'")
+            }
+            Literal::BitString(ref bits) => {
+                write!(f, "b'")?;
+                for &bit in bits {
+                    write!(f, "{}", if bit { '1' } else { '0' })?;
+                }
+                write!(f, "'")
+            }
+            Literal::CurrentTime => write!(f, "CURRENT_TIME"),
+            Literal::CurrentDate => write!(f, "CURRENT_DATE"),
+            Literal::CurrentTimestamp => write!(f, "CURRENT_TIMESTAMP"),
+            Literal::Default => write!(f, "DEFAULT"),
+            Literal::Placeholder(ref item) => write!(f, "{}", item.to_string()),
         }
     }
 }
@@ -197,8 +529,8 @@ impl From<Literal> for LiteralExpression {
 impl fmt::Display for LiteralExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.alias {
-            Some(ref alias) => write!(f, "{} AS {}", self.value.to_string(), alias),
-            None => write!(f, "{}", self.value.to_string()),
+            Some(ref alias) => write!(f, "{} AS {}", self.value, alias),
+            None => write!(f, "{}", self.value),
         }
     }
 }
@@ -211,6 +543,8 @@ pub enum Operator {
     Like,
     NotLike,
     Equal,
+    /// MySQL's `<=>`, which is like `=` but treats two NULLs as equal.
+    NullSafeEqual,
     NotEqual,
     Greater,
     GreaterOrEqual,
@@ -219,6 +553,10 @@ pub enum Operator {
     In,
     NotIn,
     Is,
+    IsNot,
+    /// `x BETWEEN lo AND hi`; the right-hand side is an `AND` tree holding both bounds.
+    Between,
+    NotBetween,
 }
 
 impl Display for Operator {
@@ -228,8 +566,9 @@ impl Display for Operator {
             Operator::And => "AND",
             Operator::Or => "OR",
             Operator::Like => "LIKE",
-            Operator::NotLike => "NOT_LIKE",
+            Operator::NotLike => "NOT LIKE",
             Operator::Equal => "=",
+            Operator::NullSafeEqual => "<=>",
             Operator::NotEqual => "!=",
             Operator::Greater => ">",
             Operator::GreaterOrEqual => ">=",
@@ -238,6 +577,9 @@ impl Display for Operator {
             Operator::In => "IN",
             Operator::NotIn => "NOT IN",
             Operator::Is => "IS",
+            Operator::IsNot => "IS NOT",
+            Operator::Between => "BETWEEN",
+            Operator::NotBetween => "NOT BETWEEN",
         };
         write!(f, "{}", op)
     }
@@ -245,28 +587,67 @@ impl Display for Operator {
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum TableKey {
-    PrimaryKey(Vec<Column>),
-    UniqueKey(Option<String>, Vec<Column>),
-    FulltextKey(Option<String>, Vec<Column>),
-    Key(String, Vec<Column>),
+    PrimaryKey {
+        columns: Vec<IndexColumn>,
+        /// The `USING` index method.
+        index_type: Option<IndexType>,
+    },
+    UniqueKey {
+        name: Option<String>,
+        columns: Vec<IndexColumn>,
+        comment: Option<String>,
+        /// The `USING` index method.
+        index_type: Option<IndexType>,
+    },
+    FulltextKey {
+        name: Option<String>,
+        columns: Vec<IndexColumn>,
+        comment: Option<String>,
+    },
+    /// A plain index.
+    Key {
+        name: String,
+        columns: Vec<IndexColumn>,
+        comment: Option<String>,
+        /// The `USING` index method.
+        index_type: Option<IndexType>,
+    },
+    ForeignKey {
+        /// The `CONSTRAINT` name.
+        name: Option<String>,
+        /// The name of the index backing the key, as in `FOREIGN KEY index_name (...)`.
+        index_name: Option<String>,
+        /// The referencing columns.
+        columns: Vec<Column>,
+        reference: ForeignKeyReference,
+    },
 }
 
 impl fmt::Display for TableKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            TableKey::PrimaryKey(ref columns) => {
+            TableKey::PrimaryKey {
+                ref columns,
+                ref index_type,
+            } => {
                 write!(f, "PRIMARY KEY ")?;
                 write!(
                     f,
                     "({})",
                     columns
                         .iter()
-                        .map(|c| escape_if_keyword(&c.name))
+                        .map(|c| format!("{}", c))
                         .collect::<Vec<_>>()
                         .join(", ")
-                )
+                )?;
+                write_index_type(f, index_type)
             }
-            TableKey::UniqueKey(ref name, ref columns) => {
+            TableKey::UniqueKey {
+                ref name,
+                ref columns,
+                ref comment,
+                ref index_type,
+            } => {
                 write!(f, "UNIQUE KEY ")?;
                 if let Some(ref name) = *name {
                     write!(f, "{} ", escape_if_keyword(name))?;
@@ -276,12 +657,18 @@ impl fmt::Display for TableKey {
                     "({})",
                     columns
                         .iter()
-                        .map(|c| escape_if_keyword(&c.name))
+                        .map(|c| format!("{}", c))
                         .collect::<Vec<_>>()
                         .join(", ")
-                )
+                )?;
+                write_index_type(f, index_type)?;
+                write_index_comment(f, comment)
             }
-            TableKey::FulltextKey(ref name, ref columns) => {
+            TableKey::FulltextKey {
+                ref name,
+                ref columns,
+                ref comment,
+            } => {
                 write!(f, "FULLTEXT KEY ")?;
                 if let Some(ref name) = *name {
                     write!(f, "{} ", escape_if_keyword(name))?;
@@ -291,27 +678,145 @@ impl fmt::Display for TableKey {
                     "({})",
                     columns
                         .iter()
-                        .map(|c| escape_if_keyword(&c.name))
+                        .map(|c| format!("{}", c))
                         .collect::<Vec<_>>()
                         .join(", ")
-                )
+                )?;
+                write_index_comment(f, comment)
             }
-            TableKey::Key(ref name, ref columns) => {
+            TableKey::Key {
+                ref name,
+                ref columns,
+                ref comment,
+                ref index_type,
+            } => {
                 write!(f, "KEY {} ", escape_if_keyword(name))?;
                 write!(
                     f,
                     "({})",
                     columns
                         .iter()
-                        .map(|c| escape_if_keyword(&c.name))
+                        .map(|c| format!("{}", c))
                         .collect::<Vec<_>>()
                         .join(", ")
+                )?;
+                write_index_type(f, index_type)?;
+                write_index_comment(f, comment)
+            }
+            TableKey::ForeignKey {
+                ref name,
+                ref index_name,
+                ref columns,
+                ref reference,
+            } => {
+                if let Some(ref name) = *name {
+                    write!(f, "CONSTRAINT {} ", escape_if_keyword(name))?;
+                }
+                write!(f, "FOREIGN KEY ")?;
+                if let Some(ref index_name) = *index_name {
+                    write!(f, "{} ", escape_if_keyword(index_name))?;
+                }
+                write!(
+                    f,
+                    "({}) {}",
+                    columns
+                        .iter()
+                        .map(|c| escape_if_keyword(&c.name))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    reference
                 )
             }
         }
     }
 }
 
+fn write_index_type(f: &mut fmt::Formatter, index_type: &Option<IndexType>) -> fmt::Result {
+    match *index_type {
+        Some(ref index_type) => write!(f, " USING {}", index_type),
+        None => Ok(()),
+    }
+}
+
+fn write_index_comment(f: &mut fmt::Formatter, comment: &Option<String>) -> fmt::Result {
+    match *comment {
+        Some(ref comment) => write!(f, " COMMENT {}", Literal::from(comment.as_str())),
+        None => Ok(()),
+    }
+}
+
+/// The data structure backing an index, as given by `USING BTREE` or `USING HASH`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum IndexType {
+    BTree,
+    Hash,
+}
+
+impl fmt::Display for IndexType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IndexType::BTree => write!(f, "BTREE"),
+            IndexType::Hash => write!(f, "HASH"),
+        }
+    }
+}
+
+/// What happens to referencing rows when a referenced row is deleted or updated.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum ReferentialAction {
+    Cascade,
+    SetNull,
+    SetDefault,
+    Restrict,
+    NoAction,
+}
+
+impl fmt::Display for ReferentialAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReferentialAction::Cascade => write!(f, "CASCADE"),
+            ReferentialAction::SetNull => write!(f, "SET NULL"),
+            ReferentialAction::SetDefault => write!(f, "SET DEFAULT"),
+            ReferentialAction::Restrict => write!(f, "RESTRICT"),
+            ReferentialAction::NoAction => write!(f, "NO ACTION"),
+        }
+    }
+}
+
+/// The target of a foreign key: `REFERENCES table [(columns)] [ON DELETE ...] [ON UPDATE ...]`.
+/// The column list is empty if omitted, which refers to the target's primary key.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ForeignKeyReference {
+    pub table: Table,
+    pub columns: Vec<Column>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+}
+
+impl fmt::Display for ForeignKeyReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "REFERENCES {}", self.table)?;
+        if !self.columns.is_empty() {
+            write!(
+                f,
+                " ({})",
+                self.columns
+                    .iter()
+                    .map(|c| escape_if_keyword(&c.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        if let Some(action) = self.on_delete {
+            write!(f, " ON DELETE {}", action)?;
+        }
+        if let Some(action) = self.on_update {
+            write!(f, " ON UPDATE {}", action)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum FieldDefinitionExpression {
     All,
@@ -343,6 +848,9 @@ impl Default for FieldDefinitionExpression {
 pub enum FieldValueExpression {
     Arithmetic(ArithmeticExpression),
     Literal(LiteralExpression),
+    Column(Column),
+    Case(CaseExpression),
+    Cast(CastExpression),
 }
 
 impl Display for FieldValueExpression {
@@ -350,24 +858,26 @@ impl Display for FieldValueExpression {
         match *self {
             FieldValueExpression::Arithmetic(ref expr) => write!(f, "{}", expr),
             FieldValueExpression::Literal(ref lit) => write!(f, "{}", lit),
+            FieldValueExpression::Column(ref col) => write!(f, "{}", col),
+            FieldValueExpression::Case(ref expr) => write!(f, "{}", expr),
+            FieldValueExpression::Cast(ref expr) => write!(f, "{}", expr),
         }
     }
 }
 
 #[inline]
+// Besides ASCII letters, digits, `_` and `@`, MySQL allows `$` and any non-ASCII character in
+// unquoted identifiers; the bytes of multi-byte UTF-8 characters are all non-ASCII.
 pub fn is_sql_identifier(chr: u8) -> bool {
-    is_alphanumeric(chr) || chr == '_' as u8 || chr == '@' as u8
+    is_alphanumeric(chr) || chr == b'_' || chr == b'@' || chr == b'$' || !chr.is_ascii()
 }
 
+// Converts a run of ASCII digits to a `u16` in a single pass, or `None` if it overflows.
 #[inline]
-fn len_as_u16(len: &[u8]) -> u16 {
-    match str::from_utf8(len) {
-        Ok(s) => match u16::from_str(s) {
-            Ok(v) => v,
-            Err(e) => std::panic::panic_any(e),
-        },
-        Err(e) => std::panic::panic_any(e),
-    }
+fn len_as_u16(len: &[u8]) -> Option<u16> {
+    len.iter().try_fold(0u16, |acc, &d| {
+        acc.checked_mul(10)?.checked_add(u16::from(d - b'0'))
+    })
 }
 
 pub(crate) fn opt_delimited<I: Clone, O1, O2, O3, E: ParseError<I>, F, G, H>(
@@ -393,25 +903,42 @@ where
     }
 }
 
-fn precision_helper(i: &[u8]) -> IResult<&[u8], (u8, Option<u8>)> {
-    let (remaining_input, (m, d)) = tuple((
-        digit1,
-        opt(preceded(tag(","), preceded(multispace0, digit1))),
-    ))(i)?;
+// A run of digits that fits in a `u8`.
+fn digits_u8(i: &[u8]) -> IResult<&[u8], u8> {
+    map_opt(digit1, |d| {
+        len_as_u16(d)
+            .filter(|&n| n <= u16::from(u8::MAX))
+            .map(|n| n as u8)
+    })(i)
+}
 
-    Ok((remaining_input, (m[0], d.map(|r| r[0]))))
+fn precision_helper(i: &[u8]) -> IResult<&[u8], (u8, Option<u8>)> {
+    tuple((digits_u8, opt(preceded(ws_sep_comma, digits_u8))))(i)
 }
 
 pub fn precision(i: &[u8]) -> IResult<&[u8], (u8, Option<u8>)> {
-    delimited(tag("("), precision_helper, tag(")"))(i)
+    delimited(
+        terminated(tag("("), multispace0),
+        precision_helper,
+        preceded(multispace0, tag(")")),
+    )(i)
 }
 
 fn opt_signed(i: &[u8]) -> IResult<&[u8], Option<&[u8]>> {
     opt(alt((tag_no_case("unsigned"), tag_no_case("signed"))))(i)
 }
 
-fn delim_digit(i: &[u8]) -> IResult<&[u8], &[u8]> {
-    delimited(tag("("), digit1, tag(")"))(i)
+pub(crate) fn delim_digit(i: &[u8]) -> IResult<&[u8], &[u8]> {
+    delimited(
+        terminated(tag("("), multispace0),
+        digit1,
+        preceded(multispace0, tag(")")),
+    )(i)
+}
+
+// A parenthesized length such as `(255)`; fails rather than panics if it does not fit in a `u16`.
+pub(crate) fn delim_u16(i: &[u8]) -> IResult<&[u8], u16> {
+    map_opt(delim_digit, len_as_u16)(i)
 }
 
 // TODO: rather than copy paste these functions, should create a function that returns a parser
@@ -419,7 +946,7 @@ fn delim_digit(i: &[u8]) -> IResult<&[u8], &[u8]> {
 fn tiny_int(i: &[u8]) -> IResult<&[u8], SqlType> {
     let (remaining_input, (_, len, _, signed)) = tuple((
         tag_no_case("tinyint"),
-        opt(delim_digit),
+        opt(delim_u16),
         multispace0,
         opt_signed,
     ))(i)?;
@@ -430,21 +957,12 @@ fn tiny_int(i: &[u8]) -> IResult<&[u8], SqlType> {
                 .unwrap()
                 .eq_ignore_ascii_case("unsigned")
             {
-                Ok((
-                    remaining_input,
-                    SqlType::UnsignedTinyint(len.map(|l| len_as_u16(l)).unwrap_or(1)),
-                ))
+                Ok((remaining_input, SqlType::UnsignedTinyint(len)))
             } else {
-                Ok((
-                    remaining_input,
-                    SqlType::Tinyint(len.map(|l| len_as_u16(l)).unwrap_or(1)),
-                ))
+                Ok((remaining_input, SqlType::Tinyint(len)))
             }
         }
-        None => Ok((
-            remaining_input,
-            SqlType::Tinyint(len.map(|l| len_as_u16(l)).unwrap_or(1)),
-        )),
+        None => Ok((remaining_input, SqlType::Tinyint(len))),
     }
 }
 
@@ -453,7 +971,7 @@ fn tiny_int(i: &[u8]) -> IResult<&[u8], SqlType> {
 fn big_int(i: &[u8]) -> IResult<&[u8], SqlType> {
     let (remaining_input, (_, len, _, signed)) = tuple((
         tag_no_case("bigint"),
-        opt(delim_digit),
+        opt(delim_u16),
         multispace0,
         opt_signed,
     ))(i)?;
@@ -464,21 +982,12 @@ fn big_int(i: &[u8]) -> IResult<&[u8], SqlType> {
                 .unwrap()
                 .eq_ignore_ascii_case("unsigned")
             {
-                Ok((
-                    remaining_input,
-                    SqlType::UnsignedBigint(len.map(|l| len_as_u16(l)).unwrap_or(1)),
-                ))
+                Ok((remaining_input, SqlType::UnsignedBigint(len)))
             } else {
-                Ok((
-                    remaining_input,
-                    SqlType::Bigint(len.map(|l| len_as_u16(l)).unwrap_or(1)),
-                ))
+                Ok((remaining_input, SqlType::Bigint(len)))
             }
         }
-        None => Ok((
-            remaining_input,
-            SqlType::Bigint(len.map(|l| len_as_u16(l)).unwrap_or(1)),
-        )),
+        None => Ok((remaining_input, SqlType::Bigint(len))),
     }
 }
 
@@ -491,7 +1000,7 @@ fn sql_int_type(i: &[u8]) -> IResult<&[u8], SqlType> {
             tag_no_case("int"),
             tag_no_case("smallint"),
         )),
-        opt(delim_digit),
+        opt(delim_u16),
         multispace0,
         opt_signed,
     ))(i)?;
@@ -502,21 +1011,12 @@ fn sql_int_type(i: &[u8]) -> IResult<&[u8], SqlType> {
                 .unwrap()
                 .eq_ignore_ascii_case("unsigned")
             {
-                Ok((
-                    remaining_input,
-                    SqlType::UnsignedInt(len.map(|l| len_as_u16(l)).unwrap_or(32)),
-                ))
+                Ok((remaining_input, SqlType::UnsignedInt(len)))
             } else {
-                Ok((
-                    remaining_input,
-                    SqlType::Int(len.map(|l| len_as_u16(l)).unwrap_or(32)),
-                ))
+                Ok((remaining_input, SqlType::Int(len)))
             }
         }
-        None => Ok((
-            remaining_input,
-            SqlType::Int(len.map(|l| len_as_u16(l)).unwrap_or(32)),
-        )),
+        None => Ok((remaining_input, SqlType::Int(len))),
     }
 }
 
@@ -538,39 +1038,48 @@ fn decimal_or_numeric(i: &[u8]) -> IResult<&[u8], SqlType> {
 }
 
 fn type_identifier_first_half(i: &[u8]) -> IResult<&[u8], SqlType> {
+    // The most common types come first, so that typical schemas need fewer attempts.
     alt((
         tiny_int,
         big_int,
         sql_int_type,
-        map(tag_no_case("bool"), |_| SqlType::Bool),
         map(
             tuple((
-                tag_no_case("char"),
-                delim_digit,
+                tag_no_case("varchar"),
+                delim_u16,
                 multispace0,
                 opt(tag_no_case("binary")),
             )),
-            |t| SqlType::Char(len_as_u16(t.1)),
+            |t| SqlType::Varchar(t.1),
         ),
-        map(preceded(tag_no_case("datetime"), opt(delim_digit)), |fsp| {
-            SqlType::DateTime(match fsp {
-                Some(fsp) => len_as_u16(fsp),
-                None => 0 as u16,
-            })
+        map(tag_no_case("text"), |_| SqlType::Text),
+        map(preceded(tag_no_case("datetime"), opt(delim_u16)), |fsp| {
+            SqlType::DateTime(fsp.unwrap_or(0))
         }),
         map(tag_no_case("date"), |_| SqlType::Date),
+        map(
+            tuple((tag_no_case("timestamp"), opt(delim_digit), multispace0)),
+            |_| SqlType::Timestamp,
+        ),
+        map(
+            terminated(tag_no_case("bool"), opt(tag_no_case("ean"))),
+            |_| SqlType::Bool,
+        ),
+        map(
+            tuple((
+                tag_no_case("char"),
+                delim_u16,
+                multispace0,
+                opt(tag_no_case("binary")),
+            )),
+            |t| SqlType::Char(t.1),
+        ),
         map(
             tuple((tag_no_case("double"), multispace0, opt_signed)),
             |_| SqlType::Double,
         ),
         map(
-            terminated(
-                preceded(
-                    tag_no_case("enum"),
-                    delimited(tag("("), value_list, tag(")")),
-                ),
-                multispace0,
-            ),
+            terminated(preceded(tag_no_case("enum"), member_list), multispace0),
             |v| SqlType::Enum(v),
         ),
         map(
@@ -586,20 +1095,6 @@ fn type_identifier_first_half(i: &[u8]) -> IResult<&[u8], SqlType> {
             tuple((tag_no_case("real"), multispace0, opt_signed)),
             |_| SqlType::Real,
         ),
-        map(tag_no_case("text"), |_| SqlType::Text),
-        map(
-            tuple((tag_no_case("timestamp"), opt(delim_digit), multispace0)),
-            |_| SqlType::Timestamp,
-        ),
-        map(
-            tuple((
-                tag_no_case("varchar"),
-                delim_digit,
-                multispace0,
-                opt(tag_no_case("binary")),
-            )),
-            |t| SqlType::Varchar(len_as_u16(t.1)),
-        ),
         decimal_or_numeric,
     ))(i)
 }
@@ -607,8 +1102,8 @@ fn type_identifier_first_half(i: &[u8]) -> IResult<&[u8], SqlType> {
 fn type_identifier_second_half(i: &[u8]) -> IResult<&[u8], SqlType> {
     alt((
         map(
-            tuple((tag_no_case("binary"), delim_digit, multispace0)),
-            |t| SqlType::Binary(len_as_u16(t.1)),
+            tuple((tag_no_case("binary"), delim_u16, multispace0)),
+            |t| SqlType::Binary(t.1),
         ),
         map(tag_no_case("blob"), |_| SqlType::Blob),
         map(tag_no_case("longblob"), |_| SqlType::Longblob),
@@ -618,21 +1113,65 @@ fn type_identifier_second_half(i: &[u8]) -> IResult<&[u8], SqlType> {
         map(tag_no_case("tinyblob"), |_| SqlType::Tinyblob),
         map(tag_no_case("tinytext"), |_| SqlType::Tinytext),
         map(
-            tuple((tag_no_case("varbinary"), delim_digit, multispace0)),
-            |t| SqlType::Varbinary(len_as_u16(t.1)),
+            tuple((tag_no_case("varbinary"), delim_u16, multispace0)),
+            |t| SqlType::Varbinary(t.1),
+        ),
+        map(tag_no_case("json"), |_| SqlType::Json),
+        map(
+            terminated(preceded(tag_no_case("set"), member_list), multispace0),
+            SqlType::Set,
         ),
+        spatial_type,
+    ))(i)
+}
+
+// Parenthesized member list of an ENUM or SET type. Members are string literals, so numeric-looking
+// members such as '01' keep their exact text.
+fn member_list(i: &[u8]) -> IResult<&[u8], Vec<Literal>> {
+    delimited(
+        terminated(tag("("), multispace0),
+        value_list,
+        preceded(multispace0, tag(")")),
+    )(i)
+}
+
+// MySQL spatial data types (§11.4.1)
+fn spatial_type(i: &[u8]) -> IResult<&[u8], SqlType> {
+    alt((
+        map(
+            alt((
+                tag_no_case("geometrycollection"),
+                tag_no_case("geomcollection"),
+            )),
+            |_| SqlType::Geometrycollection,
+        ),
+        map(tag_no_case("geometry"), |_| SqlType::Geometry),
+        map(tag_no_case("point"), |_| SqlType::Point),
+        map(tag_no_case("linestring"), |_| SqlType::Linestring),
+        map(tag_no_case("polygon"), |_| SqlType::Polygon),
+        map(tag_no_case("multipoint"), |_| SqlType::Multipoint),
+        map(tag_no_case("multilinestring"), |_| SqlType::Multilinestring),
+        map(tag_no_case("multipolygon"), |_| SqlType::Multipolygon),
     ))(i)
 }
 
 // A SQL type specifier.
 pub fn type_identifier(i: &[u8]) -> IResult<&[u8], SqlType> {
-    alt((type_identifier_first_half, type_identifier_second_half))(i)
+    let (remaining_input, sql_type) =
+        alt((type_identifier_first_half, type_identifier_second_half))(i)?;
+
+    // MySQL has no boolean type; BOOL is a synonym for TINYINT(1)
+    match (sql_type, dialect::current()) {
+        (SqlType::Bool, Some(Dialect::MySQL)) => Ok((remaining_input, SqlType::Tinyint(Some(1)))),
+        (sql_type, _) => Ok((remaining_input, sql_type)),
+    }
 }
 
 // Parses the argument for an aggregation function
 pub fn function_argument_parser(i: &[u8]) -> IResult<&[u8], FunctionArgument> {
     alt((
-        map(case_when_column, |cw| FunctionArgument::Conditional(cw)),
+        map(case_expression_no_alias, FunctionArgument::Conditional),
+        map(literal, FunctionArgument::Literal),
         map(column_identifier_no_alias, |c| FunctionArgument::Column(c)),
     ))(i)
 }
@@ -661,13 +1200,28 @@ fn group_concat_fx(i: &[u8]) -> IResult<&[u8], (Column, Option<&[u8]>)> {
 }
 
 fn delim_fx_args(i: &[u8]) -> IResult<&[u8], (FunctionArgument, bool)> {
-    delimited(tag("("), function_arguments, tag(")"))(i)
+    delimited(
+        terminated(tag("("), multispace0),
+        function_arguments,
+        preceded(multispace0, tag(")")),
+    )(i)
+}
+
+fn count_star(i: &[u8]) -> IResult<&[u8], ()> {
+    let (remaining_input, _) = tuple((
+        tag_no_case("count("),
+        multispace0,
+        tag("*"),
+        multispace0,
+        tag(")"),
+    ))(i)?;
+    Ok((remaining_input, ()))
 }
 
 pub fn column_function(i: &[u8]) -> IResult<&[u8], FunctionExpression> {
     let delim_group_concat_fx = delimited(tag("("), group_concat_fx, tag(")"));
     alt((
-        map(tag_no_case("count(*)"), |_| FunctionExpression::CountStar),
+        map(count_star, |_| FunctionExpression::CountStar),
         map(preceded(tag_no_case("count"), delim_fx_args), |args| {
             FunctionExpression::Count(args.0.clone(), args.1)
         }),
@@ -683,6 +1237,21 @@ pub fn column_function(i: &[u8]) -> IResult<&[u8], FunctionExpression> {
         map(preceded(tag_no_case("min"), delim_fx_args), |args| {
             FunctionExpression::Min(args.0.clone())
         }),
+        // `VALUES(col)` in ON DUPLICATE KEY UPDATE; VALUES is a keyword, so it is not matched below
+        map(
+            preceded(
+                terminated(tag_no_case("values"), multispace0),
+                delimited(
+                    terminated(tag("("), multispace0),
+                    column_identifier_no_function,
+                    preceded(multispace0, tag(")")),
+                ),
+            ),
+            |col| {
+                let args = vec![FunctionArgument::Column(col)];
+                FunctionExpression::Generic(String::from("VALUES"), FunctionArguments::from(args))
+            },
+        ),
         map(
             preceded(tag_no_case("group_concat"), delim_group_concat_fx),
             |spec| {
@@ -695,7 +1264,8 @@ pub fn column_function(i: &[u8]) -> IResult<&[u8], FunctionExpression> {
                 FunctionExpression::GroupConcat(FunctionArgument::Column(col.clone()), sep)
             },
         ),
-        map(tuple((sql_identifier, multispace0, tag("("), separated_list0(tag(","), delimited(multispace0, function_argument_parser, multispace0)), tag(")"))), |tuple| {
+        // REPLACE is a keyword (for `REPLACE INTO`), but also names a string function
+        map(tuple((alt((sql_identifier, tag_no_case("replace"))), multispace0, tag("("), separated_list0(tag(","), delimited(multispace0, function_argument_parser, multispace0)), tag(")"))), |tuple| {
             let (name, _, _, arguments, _) = tuple;
             FunctionExpression::Generic(
                 str::from_utf8(name).unwrap().to_string(), 
@@ -706,7 +1276,6 @@ pub fn column_function(i: &[u8]) -> IResult<&[u8], FunctionExpression> {
 
 // Parses a SQL column identifier in the table.column format
 pub fn column_identifier_no_alias(i: &[u8]) -> IResult<&[u8], Column> {
-    let table_parser = pair(opt(terminated(sql_identifier, tag("."))), sql_identifier);
     alt((
         map(column_function, |f| Column {
             name: format!("{}", f),
@@ -714,7 +1283,16 @@ pub fn column_identifier_no_alias(i: &[u8]) -> IResult<&[u8], Column> {
             table: None,
             function: Some(Box::new(f)),
         }),
-        map(table_parser, |tup| Column {
+        column_identifier_no_function,
+    ))(i)
+}
+
+// Parses a plain SQL column identifier in the table.column format, never treating a following
+// parenthesis as a function call (e.g., for index column prefix lengths like `name(10)`)
+pub fn column_identifier_no_function(i: &[u8]) -> IResult<&[u8], Column> {
+    map(
+        pair(opt(terminated(sql_identifier, tag("."))), sql_identifier),
+        |tup| Column {
             name: str::from_utf8(tup.1).unwrap().to_string(),
             alias: None,
             table: match tup.0 {
@@ -722,8 +1300,8 @@ pub fn column_identifier_no_alias(i: &[u8]) -> IResult<&[u8], Column> {
                 Some(t) => Some(str::from_utf8(t).unwrap().to_string()),
             },
             function: None,
-        }),
-    ))(i)
+        },
+    )(i)
 }
 
 // Parses a SQL column identifier in the table.column format
@@ -762,12 +1340,35 @@ pub fn column_identifier(i: &[u8]) -> IResult<&[u8], Column> {
     alt((col_func_no_table, col_w_table))(i)
 }
 
-// Parses a SQL identifier (alphanumeric1 and "_").
+// Parses a run of identifier characters. Non-ASCII characters are allowed, but only as valid
+// UTF-8, so that callers can convert the run to a `str` without checking.
+fn identifier_chars(i: &[u8]) -> IResult<&[u8], &[u8]> {
+    verify(take_while1(is_sql_identifier), |s: &[u8]| {
+        str::from_utf8(s).is_ok()
+    })(i)
+}
+
+// Parses a SQL identifier (alphanumeric1 and "_"), possibly quoted as the current dialect allows.
 pub fn sql_identifier(i: &[u8]) -> IResult<&[u8], &[u8]> {
     alt((
-        preceded(not(peek(sql_keyword)), take_while1(is_sql_identifier)),
-        delimited(tag("`"), take_while1(is_sql_identifier), tag("`")),
-        delimited(tag("["), take_while1(is_sql_identifier), tag("]")),
+        // a leading `$` begins a placeholder such as `$1`, and a leading digit a number
+        preceded(
+            tuple((not(peek(sql_keyword)), not(tag("$")), not(digit1))),
+            identifier_chars,
+        ),
+        preceded(
+            dialect_allows(|d| matches!(d, None | Some(Dialect::MySQL) | Some(Dialect::SQLite))),
+            delimited(tag("`"), identifier_chars, tag("`")),
+        ),
+        preceded(
+            dialect_allows(|d| matches!(d, None | Some(Dialect::SQLite))),
+            delimited(tag("["), identifier_chars, tag("]")),
+        ),
+        preceded(
+            // the complement of where `raw_string_double_quoted` applies
+            dialect_allows(|d| !matches!(d, None | Some(Dialect::MySQL))),
+            delimited(tag("\""), identifier_chars, tag("\"")),
+        ),
     ))(i)
 }
 
@@ -798,10 +1399,15 @@ pub fn statement_terminator(i: &[u8]) -> IResult<&[u8], ()> {
 pub fn binary_comparison_operator(i: &[u8]) -> IResult<&[u8], Operator> {
     alt((
         map(tag_no_case("not_like"), |_| Operator::NotLike),
+        map(
+            tuple((tag_no_case("not"), multispace1, tag_no_case("like"))),
+            |_| Operator::NotLike,
+        ),
         map(tag_no_case("like"), |_| Operator::Like),
         map(tag_no_case("!="), |_| Operator::NotEqual),
         map(tag_no_case("<>"), |_| Operator::NotEqual),
         map(tag_no_case(">="), |_| Operator::GreaterOrEqual),
+        map(tag_no_case("<=>"), |_| Operator::NullSafeEqual),
         map(tag_no_case("<="), |_| Operator::LessOrEqual),
         map(tag_no_case("="), |_| Operator::Equal),
         map(tag_no_case("<"), |_| Operator::Less),
@@ -824,15 +1430,18 @@ pub fn as_alias(i: &[u8]) -> IResult<&[u8], &str> {
 
 fn field_value_expr(i: &[u8]) -> IResult<&[u8], FieldValueExpression> {
     alt((
+        map(arithmetic_expression, |ae| {
+            FieldValueExpression::Arithmetic(ae)
+        }),
+        map(case_expression, FieldValueExpression::Case),
         map(literal, |l| {
             FieldValueExpression::Literal(LiteralExpression {
                 value: l.into(),
                 alias: None,
             })
         }),
-        map(arithmetic_expression, |ae| {
-            FieldValueExpression::Arithmetic(ae)
-        }),
+        map(cast_expression, FieldValueExpression::Cast),
+        map(column_identifier_no_alias, FieldValueExpression::Column),
     ))(i)
 }
 
@@ -858,6 +1467,17 @@ where
     delimited(multispace0, tag("="), multispace0)(i)
 }
 
+// Parse the `CHARACTER SET` keyword or its `CHARSET` shorthand
+pub(crate) fn charset_keyword(i: &[u8]) -> IResult<&[u8], ()> {
+    alt((
+        map(
+            tuple((tag_no_case("character"), multispace1, tag_no_case("set"))),
+            |_| (),
+        ),
+        map(tag_no_case("charset"), |_| ()),
+    ))(i)
+}
+
 pub fn assignment_expr_list(i: &[u8]) -> IResult<&[u8], Vec<(Column, FieldValueExpression)>> {
     many1(terminated(assignment_expr, opt(ws_sep_comma)))(i)
 }
@@ -878,6 +1498,12 @@ pub fn field_definition_expr(i: &[u8]) -> IResult<&[u8], Vec<FieldDefinitionExpr
             map(arithmetic_expression, |expr| {
                 FieldDefinitionExpression::Value(FieldValueExpression::Arithmetic(expr))
             }),
+            map(case_expression, |expr| {
+                FieldDefinitionExpression::Value(FieldValueExpression::Case(expr))
+            }),
+            map(cast_expression, |expr| {
+                FieldDefinitionExpression::Value(FieldValueExpression::Cast(expr))
+            }),
             map(literal_expression, |lit| {
                 FieldDefinitionExpression::Value(FieldValueExpression::Literal(lit))
             }),
@@ -887,8 +1513,7 @@ pub fn field_definition_expr(i: &[u8]) -> IResult<&[u8], Vec<FieldDefinitionExpr
     ))(i)
 }
 
-// Parse list of table names.
-// XXX(malte): add support for aliases
+// Parse list of table names, each with an optional alias.
 pub fn table_list(i: &[u8]) -> IResult<&[u8], Vec<Table>> {
     many0(terminated(schema_table_reference, opt(ws_sep_comma)))(i)
 }
@@ -904,22 +1529,48 @@ pub fn integer_literal(i: &[u8]) -> IResult<&[u8], Literal> {
     })(i)
 }
 
-fn unpack(v: &[u8]) -> i32 {
-    i32::from_str(str::from_utf8(v).unwrap()).unwrap()
-}
-
-// Floating point literal value
-pub fn float_literal(i: &[u8]) -> IResult<&[u8], Literal> {
-    map(tuple((opt(tag("-")), digit1, tag("."), digit1)), |tup| {
-        Literal::FixedPoint(Real {
-            integral: if (tup.0).is_some() {
-                -1 * unpack(tup.1)
-            } else {
-                unpack(tup.1)
-            },
-            fractional: unpack(tup.3) as i32,
-        })
-    })(i)
+// Numeric literal value: an integer, a decimal, or a number in scientific notation, each with an
+// optional sign. Integers too large for an `i64` become `UnsignedInteger`s. Decimals become
+// `FixedPoint`s unless `Real` cannot represent them exactly (as with `-0.5` or `1.05`), in which
+// case they are `Float`s like numbers with an exponent.
+pub fn numeric_literal(i: &[u8]) -> IResult<&[u8], Literal> {
+    let sign = || opt(alt((tag("-"), tag("+"))));
+    map_opt(
+        recognize(tuple((
+            sign(),
+            digit1,
+            opt(pair(tag("."), digit1)),
+            opt(tuple((alt((tag("e"), tag("E"))), sign(), digit1))),
+        ))),
+        |n| {
+            let n = str::from_utf8(n).unwrap();
+            if n.contains(['e', 'E']) {
+                return f64::from_str(n).ok().map(Literal::Float);
+            }
+            match n.find('.') {
+                None => i64::from_str(n)
+                    .map(Literal::Integer)
+                    .or_else(|_| u64::from_str(n.trim_start_matches('+')).map(Literal::from))
+                    .or_else(|_| f64::from_str(n).map(Literal::Float))
+                    .ok(),
+                Some(dot) => {
+                    let (integral, fractional) = (&n[..dot], &n[dot + 1..]);
+                    // `Real` has no negative zero and drops leading zeros of the fractional part
+                    let negative_zero =
+                        integral.starts_with('-') && i32::from_str(integral) == Ok(0);
+                    let exact =
+                        !negative_zero && (fractional == "0" || !fractional.starts_with('0'));
+                    match (exact, i32::from_str(integral), i32::from_str(fractional)) {
+                        (true, Ok(integral), Ok(fractional)) => Some(Literal::FixedPoint(Real {
+                            integral,
+                            fractional,
+                        })),
+                        _ => f64::from_str(n).ok().map(Literal::Float),
+                    }
+                }
+            }
+        },
+    )(i)
 }
 
 /// String literal value
@@ -963,8 +1614,12 @@ fn raw_string_single_quoted(i: &[u8]) -> IResult<&[u8], Vec<u8>> {
     raw_string_quoted(i, true)
 }
 
+// Double quotes delimit strings only in MySQL; elsewhere they quote identifiers.
 fn raw_string_double_quoted(i: &[u8]) -> IResult<&[u8], Vec<u8>> {
-    raw_string_quoted(i, false)
+    preceded(
+        dialect_allows(|d| matches!(d, None | Some(Dialect::MySQL))),
+        |i| raw_string_quoted(i, false),
+    )(i)
 }
 
 pub fn string_literal(i: &[u8]) -> IResult<&[u8], Literal> {
@@ -977,18 +1632,81 @@ pub fn string_literal(i: &[u8]) -> IResult<&[u8], Literal> {
     )(i)
 }
 
+// Converts hexadecimal digits to bytes. An odd number of digits is an error unless `pad` is set,
+// in which case a leading zero is assumed.
+fn hex_bytes(hex: &[u8], pad: bool) -> Option<Literal> {
+    let hex = str::from_utf8(hex).unwrap();
+    let hex = match (hex.len().is_multiple_of(2), pad) {
+        (true, _) => hex.to_owned(),
+        (false, true) => format!("0{}", hex),
+        (false, false) => return None,
+    };
+    (0..hex.len())
+        .step_by(2)
+        .map(|n| u8::from_str_radix(&hex[n..n + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()
+        .map(Literal::Blob)
+}
+
+// Hexadecimal blob literal value, `X'0a1b'` or `0x0a1b`. As in MySQL, the quoted form requires an
+// even number of digits, while the other is padded with a leading zero.
+pub fn blob_literal(i: &[u8]) -> IResult<&[u8], Literal> {
+    let is_hex = |c: u8| c.is_ascii_hexdigit();
+    alt((
+        map_opt(
+            delimited(
+                pair(alt((tag("X"), tag("x"))), tag("'")),
+                take_while(is_hex),
+                tag("'"),
+            ),
+            |hex| hex_bytes(hex, false),
+        ),
+        map_opt(preceded(tag("0x"), take_while1(is_hex)), |hex| {
+            hex_bytes(hex, true)
+        }),
+    ))(i)
+}
+
+// Bit-value literal, `b'0101'` or `0b0101`.
+pub fn bit_string_literal(i: &[u8]) -> IResult<&[u8], Literal> {
+    let is_bit = |c: u8| c == b'0' || c == b'1';
+    map(
+        alt((
+            delimited(
+                pair(alt((tag("b"), tag("B"))), tag("'")),
+                take_while(is_bit),
+                tag("'"),
+            ),
+            preceded(tag("0b"), take_while1(is_bit)),
+        )),
+        |bits: &[u8]| Literal::BitString(bits.iter().map(|&b| b == b'1').collect()),
+    )(i)
+}
+
 // Any literal value.
+// Parse rule for a literal spelled as a keyword, such as NULL, which must not be the start of a
+// longer identifier such as `nullable`.
+fn keyword_literal<'a>(
+    keyword: &'static str,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], &'a [u8]> {
+    terminated(
+        tag_no_case(keyword),
+        not(peek(take_while1(is_sql_identifier))),
+    )
+}
+
 pub fn literal(i: &[u8]) -> IResult<&[u8], Literal> {
     alt((
-        float_literal,
-        integer_literal,
+        blob_literal,
+        bit_string_literal,
+        numeric_literal,
         string_literal,
-        map(tag_no_case("null"), |_| Literal::Null),
-        map(tag_no_case("current_timestamp"), |_| {
+        map(keyword_literal("null"), |_| Literal::Null),
+        map(keyword_literal("current_timestamp"), |_| {
             Literal::CurrentTimestamp
         }),
-        map(tag_no_case("current_date"), |_| Literal::CurrentDate),
-        map(tag_no_case("current_time"), |_| Literal::CurrentTime),
+        map(keyword_literal("current_date"), |_| Literal::CurrentDate),
+        map(keyword_literal("current_time"), |_| Literal::CurrentTime),
         map(tag("?"), |_| {
             Literal::Placeholder(ItemPlaceholder::QuestionMark)
         }),
@@ -996,6 +1714,10 @@ pub fn literal(i: &[u8]) -> IResult<&[u8], Literal> {
             let value = i32::from_str(str::from_utf8(num).unwrap()).unwrap();
             Literal::Placeholder(ItemPlaceholder::ColonNumber(value))
         }),
+        map(preceded(tag(":"), identifier_chars), |name| {
+            let name = String::from(str::from_utf8(name).unwrap());
+            Literal::Placeholder(ItemPlaceholder::ColonName(name))
+        }),
         map(preceded(tag("$"), digit1), |num| {
             let value = i32::from_str(str::from_utf8(num).unwrap()).unwrap();
             Literal::Placeholder(ItemPlaceholder::DollarNumber(value))
@@ -1018,25 +1740,47 @@ pub fn value_list(i: &[u8]) -> IResult<&[u8], Vec<Literal>> {
     many0(delimited(multispace0, literal, opt(ws_sep_comma)))(i)
 }
 
-// Parse a reference to a named schema.table, with an optional alias
-pub fn schema_table_reference(i: &[u8]) -> IResult<&[u8], Table> {
+// Parse a table name qualified by up to two dotted parts (table, schema.table or
+// catalog.schema.table), without an alias. MySQL and SQLite have no catalog part.
+pub fn qualified_table_name(i: &[u8]) -> IResult<&[u8], Table> {
     map(
-		tuple((
-			opt(pair(sql_identifier, tag("."))),
-			sql_identifier,
-			opt(as_alias)
-		)),
-	|tup| Table {
-        name: String::from(str::from_utf8(tup.1).unwrap()),
-        alias: match tup.2 {
-            Some(a) => Some(String::from(a)),
-            None => None,
+        tuple((
+            sql_identifier,
+            opt(preceded(tag("."), sql_identifier)),
+            opt(preceded(
+                pair(
+                    dialect_allows(|d| !matches!(d, Some(Dialect::MySQL) | Some(Dialect::SQLite))),
+                    tag("."),
+                ),
+                sql_identifier,
+            )),
+        )),
+        |parts| {
+            let to_string = |s: &[u8]| String::from(str::from_utf8(s).unwrap());
+            let (catalog, schema, name) = match parts {
+                (c, Some(s), Some(t)) => (Some(c), Some(s), t),
+                (s, Some(t), None) => (None, Some(s), t),
+                (t, _, _) => (None, None, t),
+            };
+            Table {
+                name: to_string(name),
+                alias: None,
+                schema: schema.map(to_string),
+                catalog: catalog.map(to_string),
+            }
         },
-        schema: match tup.0 {
-            Some((schema, _)) => Some(String::from(str::from_utf8(schema).unwrap())),
-            None => None,
+    )(i)
+}
+
+// Parse a reference to a named [catalog.][schema.]table, with an optional alias
+pub fn schema_table_reference(i: &[u8]) -> IResult<&[u8], Table> {
+    map(
+        pair(qualified_table_name, opt(as_alias)),
+        |(table, alias)| Table {
+            alias: alias.map(String::from),
+            ..table
         },
-    })(i)
+    )(i)
 }
 
 // Parse a reference to a named table, with an optional alias
@@ -1048,17 +1792,58 @@ pub fn table_reference(i: &[u8]) -> IResult<&[u8], Table> {
             None => None,
         },
 		schema: None,
+        catalog: None,
     })(i)
 }
 
+// Parse a parenthesized expression, returning the raw text between the outermost parentheses.
+// Nested parentheses and quoted strings are skipped over, but not otherwise interpreted.
+pub fn raw_parenthesized_expr(i: &[u8]) -> IResult<&[u8], &[u8]> {
+    if i.first() != Some(&b'(') {
+        return Err(nom::Err::Error(ParseError::from_error_kind(
+            i,
+            ErrorKind::Tag,
+        )));
+    }
+    let mut depth = 0;
+    let mut quote: Option<u8> = None;
+    let mut escaped = false;
+    for (idx, &c) in i.iter().enumerate() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(q) if q != b'`' && c == b'\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None => match c {
+                b'\'' | b'"' | b'`' => quote = Some(c),
+                b'(' => depth += 1,
+                b')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok((&i[idx + 1..], &i[1..idx]));
+                    }
+                }
+                _ => (),
+            },
+        }
+    }
+    Err(nom::Err::Error(ParseError::from_error_kind(
+        i,
+        ErrorKind::Char,
+    )))
+}
+
 // Parse rule for a comment part.
 pub fn parse_comment(i: &[u8]) -> IResult<&[u8], String> {
-    map(
+    map_opt(
         preceded(
             delimited(multispace0, tag_no_case("comment"), multispace1),
-            delimited(tag("'"), take_until("'"), tag("'")),
+            string_literal,
         ),
-        |comment| String::from(str::from_utf8(comment).unwrap()),
+        |comment| match comment {
+            Literal::String(s) => Some(s),
+            _ => None,
+        },
     )(i)
 }
 
@@ -1066,6 +1851,38 @@ pub fn parse_comment(i: &[u8]) -> IResult<&[u8], String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn extended_identifier_characters() {
+        for id in &["_tmp", "col$1", "MixedCase", "café", "__"] {
+            assert_eq!(
+                sql_identifier(id.as_bytes()),
+                Ok((&b""[..], id.as_bytes())),
+                "{}",
+                id
+            );
+        }
+        assert!(sql_identifier(b"$1").is_err());
+    }
+
+    #[test]
+    fn identifiers_with_digits() {
+        for id in &["col2", "x1y2", "c0"] {
+            assert_eq!(
+                sql_identifier(id.as_bytes()),
+                Ok((&b""[..], id.as_bytes())),
+                "{}",
+                id
+            );
+        }
+        assert!(sql_identifier(b"2col").is_err());
+        assert!(sql_identifier(b"42").is_err());
+        assert_eq!(sql_identifier(b"`2col`").unwrap().1, &b"2col"[..]);
+        assert_eq!(
+            column_identifier_no_alias(b"t.col$1 ").unwrap().1,
+            Column::from("t.col$1")
+        );
+    }
+
     #[test]
     fn sql_identifiers() {
         let id1 = b"foo";
@@ -1083,6 +1900,31 @@ mod tests {
         assert!(sql_identifier(id6).is_ok());
     }
 
+    #[test]
+    fn qualified_column_identifiers() {
+        let qualified = Column {
+            name: String::from("id"),
+            alias: None,
+            table: Some(String::from("users")),
+            function: None,
+        };
+        let bare = Column {
+            name: String::from("id"),
+            alias: None,
+            table: None,
+            function: None,
+        };
+
+        let res = column_identifier(b"users.id");
+        assert_eq!(res, Ok((&b""[..], qualified.clone())));
+        let res = column_identifier(b"`users`.`id`");
+        assert_eq!(res, Ok((&b""[..], qualified.clone())));
+        let res = column_identifier(b"users.`id`");
+        assert_eq!(res, Ok((&b""[..], qualified)));
+        let res = column_identifier(b"id");
+        assert_eq!(res, Ok((&b""[..], bare)));
+    }
+
     fn test_opt_delimited_fn_call(i: &str) -> IResult<&[u8], &[u8]> {
         opt_delimited(tag("("), tag("abc"), tag(")"))(i.as_bytes())
     }
@@ -1122,12 +1964,335 @@ mod tests {
 
         assert_eq!(
             res_ok,
-            vec![SqlType::Bool, SqlType::Int(16), SqlType::DateTime(16)]
+            vec![SqlType::Bool, SqlType::Int(Some(16)), SqlType::DateTime(16)]
         );
 
         assert!(res_not_ok.into_iter().all(|r| r == false));
     }
 
+    #[test]
+    fn enum_and_set_members_stay_strings() {
+        let res = type_identifier(b"enum('01','02')");
+        assert_eq!(
+            res.unwrap().1,
+            SqlType::Enum(vec![
+                Literal::String(String::from("01")),
+                Literal::String(String::from("02")),
+            ])
+        );
+        let res = type_identifier(b"SET( '1', '2' )");
+        assert_eq!(
+            res.unwrap().1,
+            SqlType::Set(vec![
+                Literal::String(String::from("1")),
+                Literal::String(String::from("2")),
+            ])
+        );
+    }
+
+    #[test]
+    fn oversized_type_length_is_an_error() {
+        assert_eq!(
+            type_identifier(b"varchar(65535)").unwrap().1,
+            SqlType::Varchar(65535)
+        );
+        assert!(type_identifier(b"varchar(65536)").is_err());
+        assert!(type_identifier(b"binary(70000)").is_err());
+
+        // optional lengths are left unconsumed, so the enclosing statement fails to parse
+        for ty in &["int(99999999999999999999)", "datetime(70000)"] {
+            let qstring = format!("CREATE TABLE t (x {})", ty);
+            assert!(::create::creation(qstring.as_bytes()).is_err());
+        }
+    }
+
+    #[test]
+    fn numeric_literals() {
+        let parse = |n: &str| numeric_literal(n.as_bytes()).unwrap().1;
+        let fixed = |integral, fractional| {
+            Literal::FixedPoint(Real {
+                integral,
+                fractional,
+            })
+        };
+
+        assert_eq!(parse("42"), Literal::Integer(42));
+        assert_eq!(parse("-42"), Literal::Integer(-42));
+        assert_eq!(parse("+42"), Literal::Integer(42));
+        assert_eq!(
+            parse("18446744073709551615"),
+            Literal::UnsignedInteger(u64::MAX)
+        );
+        assert_eq!(parse("3.14"), fixed(3, 14));
+        assert_eq!(parse("-3.0"), fixed(-3, 0));
+        assert_eq!(parse("1.05"), Literal::Float(1.05));
+        assert_eq!(parse("-0.5"), Literal::Float(-0.5));
+        assert_eq!(parse("1e3"), Literal::Float(1000.0));
+        assert_eq!(parse("-2.5E-2"), Literal::Float(-0.025));
+
+        // an exponent needs digits; a dangling one is left unparsed
+        assert_eq!(
+            numeric_literal(b"7e").unwrap(),
+            (&b"e"[..], Literal::Integer(7))
+        );
+        assert!(numeric_literal(b"-x").is_err());
+    }
+
+    #[test]
+    fn decimal_precision_and_scale() {
+        let parse = |t: &str| match type_identifier(t.as_bytes()) {
+            Ok((&[], t)) => Some(t),
+            _ => None,
+        };
+        assert_eq!(parse("decimal(10,2)"), Some(SqlType::Decimal(10, 2)));
+        assert_eq!(parse("NUMERIC(65, 30)"), Some(SqlType::Decimal(65, 30)));
+        assert_eq!(parse("decimal(7)"), Some(SqlType::Decimal(7, 0)));
+        assert_eq!(parse("decimal(256)"), None);
+    }
+
+    #[test]
+    fn literal_kinds_round_trip() {
+        let cases = vec![
+            ("NULL", Literal::Null),
+            ("-7", Literal::Integer(-7)),
+            ("18446744073709551615", Literal::UnsignedInteger(u64::MAX)),
+            (
+                "2.5",
+                Literal::FixedPoint(Real {
+                    integral: 2,
+                    fractional: 5,
+                }),
+            ),
+            ("1.5e-3", Literal::Float(0.0015)),
+            ("-0.5", Literal::Float(-0.5)),
+            ("1.05", Literal::Float(1.05)),
+            ("1e300", Literal::Float(1e300)),
+            ("'it''s'", Literal::String(String::from("it's"))),
+            ("'a\\\\b'", Literal::String(String::from("a\\b"))),
+            ("X'00ff'", Literal::Blob(vec![0x00, 0xff])),
+            ("b'01'", Literal::BitString(vec![false, true])),
+            ("CURRENT_TIME", Literal::CurrentTime),
+            ("CURRENT_DATE", Literal::CurrentDate),
+            ("CURRENT_TIMESTAMP", Literal::CurrentTimestamp),
+            ("?", Literal::Placeholder(ItemPlaceholder::QuestionMark)),
+            (
+                ":id",
+                Literal::Placeholder(ItemPlaceholder::ColonName("id".into())),
+            ),
+        ];
+        for (text, expected) in cases {
+            let (rest, parsed) = literal(text.as_bytes()).unwrap();
+            assert!(rest.is_empty(), "{} left {:?}", text, rest);
+            assert_eq!(parsed, expected);
+            assert_eq!(format!("{}", parsed), text);
+        }
+
+        assert!(blob_literal(b"X'abc'").is_err());
+    }
+
+    #[test]
+    fn hex_and_bit_literals() {
+        let parse = |l: &str| literal(l.as_bytes()).unwrap().1;
+
+        assert_eq!(parse("0xFF"), Literal::Blob(vec![0xff]));
+        assert_eq!(
+            parse("0xDEADBEEF"),
+            Literal::Blob(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(parse("0xabc"), Literal::Blob(vec![0x0a, 0xbc]));
+        assert_eq!(parse("x'4869'"), Literal::Blob(b"Hi".to_vec()));
+        assert_eq!(parse("X''"), Literal::Blob(vec![]));
+        assert_eq!(parse("b'101'"), Literal::BitString(vec![true, false, true]));
+        assert_eq!(parse("0b0011"), parse("B'0011'"));
+        assert_eq!(format!("{}", parse("0b0011")), "b'0011'");
+
+        assert!(blob_literal(b"x'4g'").is_err());
+        assert!(bit_string_literal(b"b'102'").is_err());
+    }
+
+    #[test]
+    fn type_families() {
+        assert_eq!(SqlType::Varchar(255).family(), SqlTypeFamily::String);
+        assert_eq!(SqlType::Char(10).family(), SqlType::Varchar(255).family());
+        assert_eq!(SqlType::Int(Some(32)).family(), SqlTypeFamily::Numeric);
+        assert_eq!(SqlType::Decimal(10, 2).family(), SqlTypeFamily::Numeric);
+        assert_eq!(SqlType::Varbinary(16).family(), SqlTypeFamily::Binary);
+        assert_eq!(SqlType::DateTime(0).family(), SqlTypeFamily::Temporal);
+        assert_eq!(SqlType::Point.family(), SqlTypeFamily::Spatial);
+
+        let mut families: Vec<_> = [SqlType::Text, SqlType::Bool, SqlType::Bigint(Some(64))]
+            .iter()
+            .map(SqlType::family)
+            .collect();
+        families.sort();
+        assert_eq!(
+            families,
+            vec![
+                SqlTypeFamily::Boolean,
+                SqlTypeFamily::Numeric,
+                SqlTypeFamily::String
+            ]
+        );
+    }
+
+    #[test]
+    fn max_length() {
+        assert_eq!(SqlType::Varchar(255).max_length(), Some(255));
+        assert_eq!(SqlType::Char(10).max_length(), Some(10));
+        assert_eq!(SqlType::Binary(16).max_length(), Some(16));
+        assert_eq!(SqlType::Longtext.max_length(), Some(u32::MAX));
+        assert_eq!(SqlType::Blob.max_length(), Some(65_535));
+        assert_eq!(SqlType::Int(Some(11)).max_length(), None);
+        assert_eq!(SqlType::Date.max_length(), None);
+    }
+
+    #[test]
+    fn enum_index() {
+        let (_, ty) = type_identifier(b"enum('small','medium','large')").unwrap();
+        assert_eq!(ty.enum_index("medium"), Some(2));
+        assert_eq!(ty.enum_index("LARGE"), Some(3));
+        assert_eq!(ty.enum_index("huge"), None);
+        assert_eq!(SqlType::Text.enum_index("small"), None);
+    }
+
+    #[test]
+    fn integer_display_widths() {
+        for (written, sql_type, shown) in &[
+            ("int", SqlType::Int(None), "INT"),
+            ("int(11)", SqlType::Int(Some(11)), "INT(11)"),
+            (
+                "bigint unsigned",
+                SqlType::UnsignedBigint(None),
+                "BIGINT UNSIGNED",
+            ),
+            ("tinyint(1)", SqlType::Tinyint(Some(1)), "TINYINT(1)"),
+        ] {
+            assert_eq!(type_identifier(written.as_bytes()).unwrap().1, *sql_type);
+            assert_eq!(format!("{}", sql_type), *shown);
+        }
+    }
+
+    #[test]
+    fn sql_type_widening() {
+        // widenings
+        assert!(SqlType::Int(Some(11)).is_widening_to(&SqlType::Bigint(Some(20))));
+        assert!(SqlType::Varchar(100).is_widening_to(&SqlType::Varchar(200)));
+        assert!(SqlType::Char(10).is_widening_to(&SqlType::Varchar(10)));
+        assert!(SqlType::UnsignedInt(Some(10)).is_widening_to(&SqlType::Bigint(Some(20))));
+        assert!(SqlType::Int(Some(11)).is_widening_to(&SqlType::Decimal(12, 2)));
+        assert!(SqlType::Decimal(5, 2).is_widening_to(&SqlType::Decimal(8, 3)));
+        assert!(SqlType::Date.is_widening_to(&SqlType::DateTime(0)));
+        // display widths do not matter
+        assert!(SqlType::Int(Some(11)).is_widening_to(&SqlType::Int(Some(4))));
+        // a scale above the precision, as accepted by the parser, leaves no integer digits
+        let (_, odd) = type_identifier(b"DECIMAL(2,5)").unwrap();
+        assert!(odd.is_widening_to(&SqlType::Decimal(5, 5)));
+        assert!(!odd.is_widening_to(&SqlType::Decimal(5, 4)));
+
+        // narrowings
+        assert!(!SqlType::Bigint(Some(20)).is_widening_to(&SqlType::Int(Some(11))));
+        assert!(!SqlType::Varchar(200).is_widening_to(&SqlType::Varchar(100)));
+        assert!(!SqlType::Decimal(8, 3).is_widening_to(&SqlType::Decimal(8, 2)));
+        assert!(!SqlType::Varchar(10).is_widening_to(&SqlType::Char(10)));
+
+        // incompatible changes
+        assert!(!SqlType::Int(Some(11)).is_widening_to(&SqlType::UnsignedBigint(Some(20))));
+        assert!(!SqlType::Varchar(10).is_widening_to(&SqlType::Int(Some(11))));
+        assert!(!SqlType::Blob.is_widening_to(&SqlType::Text));
+        assert!(!SqlType::Bigint(Some(20)).is_widening_to(&SqlType::Double));
+    }
+
+    #[test]
+    fn all_simple_variants_parse() {
+        let variants = SqlType::all_simple_variants();
+        assert!(!variants.is_empty());
+        for t in variants {
+            // exhaustive, so that any new variant has to be classified here (and, if it takes no
+            // parameters, added to `all_simple_variants`)
+            match t {
+                SqlType::Char(_)
+                | SqlType::Varchar(_)
+                | SqlType::Int(_)
+                | SqlType::UnsignedInt(_)
+                | SqlType::Bigint(_)
+                | SqlType::UnsignedBigint(_)
+                | SqlType::Tinyint(_)
+                | SqlType::UnsignedTinyint(_)
+                | SqlType::DateTime(_)
+                | SqlType::Binary(_)
+                | SqlType::Varbinary(_)
+                | SqlType::Enum(_)
+                | SqlType::Set(_)
+                | SqlType::Decimal(..) => panic!("{:?} is parameterized", t),
+                SqlType::Bool
+                | SqlType::Blob
+                | SqlType::Longblob
+                | SqlType::Mediumblob
+                | SqlType::Tinyblob
+                | SqlType::Double
+                | SqlType::Float
+                | SqlType::Real
+                | SqlType::Tinytext
+                | SqlType::Mediumtext
+                | SqlType::Longtext
+                | SqlType::Text
+                | SqlType::Date
+                | SqlType::Timestamp
+                | SqlType::Json
+                | SqlType::Geometry
+                | SqlType::Point
+                | SqlType::Linestring
+                | SqlType::Polygon
+                | SqlType::Multipoint
+                | SqlType::Multilinestring
+                | SqlType::Multipolygon
+                | SqlType::Geometrycollection => (),
+            }
+            let rendered = format!("{}", t);
+            let res = type_identifier(rendered.as_bytes());
+            assert_eq!(res.unwrap().1, t, "{} did not round-trip", rendered);
+        }
+    }
+
+    #[test]
+    fn json_and_spatial_types() {
+        let ok = [
+            "json",
+            "geometry",
+            "point",
+            "multipolygon",
+            "geometrycollection",
+        ];
+        let res_ok: Vec<_> = ok
+            .iter()
+            .map(|t| type_identifier(t.as_bytes()).unwrap().1)
+            .collect();
+
+        assert_eq!(
+            res_ok,
+            vec![
+                SqlType::Json,
+                SqlType::Geometry,
+                SqlType::Point,
+                SqlType::Multipolygon,
+                SqlType::Geometrycollection,
+            ]
+        );
+    }
+
+    #[test]
+    fn raw_parenthesized_expressions() {
+        let res = raw_parenthesized_expr(b"(ST_GeomFromText('POINT(0 0)')) NOT NULL");
+        assert_eq!(
+            res,
+            Ok((&b" NOT NULL"[..], &b"ST_GeomFromText('POINT(0 0)')"[..]))
+        );
+        let res = raw_parenthesized_expr(b"(concat(a, ')'), b)");
+        assert_eq!(res, Ok((&b""[..], &b"concat(a, ')'), b"[..])));
+        assert!(raw_parenthesized_expr(b"(a + (b)").is_err());
+        assert!(raw_parenthesized_expr(b"a + b").is_err());
+    }
+
     #[test]
     fn simple_column_function() {
         let qs = b"max(addr_id)";
@@ -1160,10 +2325,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn function_arguments_starting_with_keyword_literals() {
+        let res = column_function(b"count(nullable)");
+        assert_eq!(
+            res,
+            Ok((
+                &b""[..],
+                FunctionExpression::Count(
+                    FunctionArgument::Column(Column::from("nullable")),
+                    false
+                )
+            ))
+        );
+        let res = column_function(b"lower(nullable)");
+        assert_eq!(
+            res,
+            Ok((
+                &b""[..],
+                FunctionExpression::Generic(
+                    "lower".to_string(),
+                    FunctionArguments::from(vec![FunctionArgument::Column(Column::from(
+                        "nullable"
+                    ))])
+                )
+            ))
+        );
+        let res = column_function(b"sum(current_timestamp_x)");
+        assert_eq!(
+            res,
+            Ok((
+                &b""[..],
+                FunctionExpression::Sum(
+                    FunctionArgument::Column(Column::from("current_timestamp_x")),
+                    false
+                )
+            ))
+        );
+
+        // the keywords on their own are still literals
+        let res = column_function(b"coalesce(null, current_timestamp)");
+        assert_eq!(
+            res,
+            Ok((
+                &b""[..],
+                FunctionExpression::Generic(
+                    "coalesce".to_string(),
+                    FunctionArguments::from(vec![
+                        FunctionArgument::Literal(Literal::Null),
+                        FunctionArgument::Literal(Literal::CurrentTimestamp),
+                    ])
+                )
+            ))
+        );
+    }
+
     #[test]
     fn comment_data() {
         let res = parse_comment(b" COMMENT 'test'");
         assert_eq!(res.unwrap().1, "test");
+        let res = parse_comment(b" COMMENT 'it''s'");
+        assert_eq!(res.unwrap().1, "it's");
     }
 
     #[test]
@@ -1196,4 +2418,72 @@ mod tests {
         let res = statement_terminator(b"   ;  ");
         assert_eq!(res, Ok((&b""[..], ())));
     }
+
+    // Property test checking that the Display output of any `SqlType` parses back to that type.
+    mod type_round_trip {
+        use super::*;
+        use proptest::collection::vec;
+        use proptest::option;
+        use proptest::prelude::*;
+        use proptest::sample::select;
+
+        // ENUM and SET members, including quotes and backslashes that must be escaped.
+        fn members() -> impl Strategy<Value = Vec<Literal>> {
+            vec("[a-zA-Z0-9 _'\\\\]{0,8}".prop_map(Literal::String), 1..6)
+        }
+
+        // Types carrying a length or a fractional seconds precision.
+        fn length_type() -> impl Strategy<Value = SqlType> {
+            prop_oneof![
+                any::<u16>().prop_map(SqlType::Char),
+                any::<u16>().prop_map(SqlType::Varchar),
+                any::<u16>().prop_map(SqlType::Binary),
+                any::<u16>().prop_map(SqlType::Varbinary),
+                (0u16..=6).prop_map(SqlType::DateTime),
+            ]
+        }
+
+        // Integer types, with or without a display width.
+        fn integer_type() -> impl Strategy<Value = SqlType> {
+            prop_oneof![
+                option::of(any::<u16>()).prop_map(SqlType::Int),
+                option::of(any::<u16>()).prop_map(SqlType::UnsignedInt),
+                option::of(any::<u16>()).prop_map(SqlType::Bigint),
+                option::of(any::<u16>()).prop_map(SqlType::UnsignedBigint),
+                option::of(any::<u16>()).prop_map(SqlType::Tinyint),
+                option::of(any::<u16>()).prop_map(SqlType::UnsignedTinyint),
+            ]
+        }
+
+        // DECIMAL with a precision of 1 to 65 digits, and a scale no larger than the precision.
+        fn decimal_type() -> impl Strategy<Value = SqlType> {
+            (1u8..=65)
+                .prop_flat_map(|m| (Just(m), 0..=m))
+                .prop_map(|(m, d)| SqlType::Decimal(m, d))
+        }
+
+        fn sql_type() -> impl Strategy<Value = SqlType> {
+            prop_oneof![
+                select(SqlType::all_simple_variants()),
+                length_type(),
+                integer_type(),
+                decimal_type(),
+                members().prop_map(SqlType::Enum),
+                members().prop_map(SqlType::Set),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn display_parses_back(sql_type in sql_type()) {
+                let displayed = sql_type.to_string();
+                prop_assert_eq!(
+                    type_identifier(displayed.as_bytes()),
+                    Ok((&b""[..], sql_type)),
+                    "{}",
+                    displayed
+                );
+            }
+        }
+    }
 }
@@ -23,7 +23,7 @@ pub enum CompoundSelectOperator {
 impl fmt::Display for CompoundSelectOperator {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            CompoundSelectOperator::Union => write!(f, "UNION"),
+            CompoundSelectOperator::Union => write!(f, "UNION ALL"),
             CompoundSelectOperator::DistinctUnion => write!(f, "UNION DISTINCT"),
             CompoundSelectOperator::Intersect => write!(f, "INTERSECT"),
             CompoundSelectOperator::Except => write!(f, "EXCEPT"),
@@ -40,17 +40,19 @@ pub struct CompoundSelectStatement {
 
 impl fmt::Display for CompoundSelectStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for (ref op, ref sel) in &self.selects {
-            if op.is_some() {
-                write!(f, " {}", op.as_ref().unwrap())?;
+        for (i, (op, sel)) in self.selects.iter().enumerate() {
+            if let Some(ref op) = *op {
+                write!(f, " {} ", op)?;
+            } else if i > 0 {
+                write!(f, " ")?;
             }
-            write!(f, " {}", sel)?;
+            write!(f, "{}", sel)?;
         }
-        if self.order.is_some() {
-            write!(f, " {}", self.order.as_ref().unwrap())?;
+        if let Some(ref order) = self.order {
+            write!(f, " {}", order)?;
         }
-        if self.limit.is_some() {
-            write!(f, " {}", self.order.as_ref().unwrap())?;
+        if let Some(ref limit) = self.limit {
+            write!(f, " {}", limit)?;
         }
         Ok(())
     }
@@ -287,4 +289,31 @@ mod tests {
 
         assert_eq!(res.unwrap().1, expected);
     }
+
+    #[test]
+    fn format_compound_select() {
+        for qstr in &[
+            "SELECT id FROM Vote UNION ALL SELECT id FROM Rating",
+            "SELECT id FROM Vote UNION DISTINCT SELECT id FROM Rating",
+            "SELECT id FROM Vote INTERSECT SELECT id FROM Rating",
+            "SELECT id FROM Vote EXCEPT SELECT id FROM Rating",
+        ] {
+            let res = compound_selection(qstr.as_bytes()).unwrap().1;
+            assert_eq!(format!("{}", res), *qstr);
+        }
+
+        let res = CompoundSelectStatement {
+            limit: Some(LimitClause {
+                limit: 5,
+                offset: 0,
+            }),
+            ..compound_selection(b"SELECT id FROM Vote UNION SELECT id FROM Rating")
+                .unwrap()
+                .1
+        };
+        assert_eq!(
+            format!("{}", res),
+            "SELECT id FROM Vote UNION DISTINCT SELECT id FROM Rating LIMIT 5"
+        );
+    }
 }
@@ -4,14 +4,17 @@ use std::fmt;
 use std::str;
 
 use arithmetic::{arithmetic_expression, ArithmeticExpression};
+use case::{case_expression, CaseExpression};
+use cast::{cast_expression, CastExpression};
 use column::Column;
 use common::{
-    binary_comparison_operator, column_identifier, literal, value_list, Literal, Operator,
+    binary_comparison_operator, column_identifier, literal, string_literal, value_list, Literal,
+    Operator,
 };
 
 use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
-use nom::combinator::{map, opt};
+use nom::combinator::{map, map_opt, opt};
 use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
 use nom::IResult;
 use select::{nested_selection, SelectStatement};
@@ -22,13 +25,15 @@ pub enum ConditionBase {
     Literal(Literal),
     LiteralList(Vec<Literal>),
     NestedSelect(Box<SelectStatement>),
+    /// A string LIKE pattern, with the character given by its ESCAPE clause, if any.
+    LikePattern(String, Option<char>),
 }
 
 impl fmt::Display for ConditionBase {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             ConditionBase::Field(ref col) => write!(f, "{}", col),
-            ConditionBase::Literal(ref literal) => write!(f, "{}", literal.to_string()),
+            ConditionBase::Literal(ref literal) => write!(f, "{}", literal),
             ConditionBase::LiteralList(ref ll) => write!(
                 f,
                 "({})",
@@ -38,6 +43,14 @@ impl fmt::Display for ConditionBase {
                     .join(", ")
             ),
             ConditionBase::NestedSelect(ref select) => write!(f, "{}", select),
+            ConditionBase::LikePattern(ref pattern, escape) => {
+                write!(f, "{}", Literal::String(pattern.clone()))?;
+                match escape {
+                    Some('\\') => write!(f, " ESCAPE '\\\\'"),
+                    Some(c) => write!(f, " ESCAPE {}", Literal::String(c.to_string())),
+                    None => Ok(()),
+                }
+            }
         }
     }
 }
@@ -93,6 +106,8 @@ pub enum ConditionExpression {
     Base(ConditionBase),
     Arithmetic(Box<ArithmeticExpression>),
     Bracketed(Box<ConditionExpression>),
+    Case(Box<CaseExpression>),
+    Cast(Box<CastExpression>),
 }
 
 impl fmt::Display for ConditionExpression {
@@ -105,6 +120,8 @@ impl fmt::Display for ConditionExpression {
             ConditionExpression::Bracketed(ref expr) => write!(f, "({})", expr),
             ConditionExpression::Base(ref base) => write!(f, "{}", base),
             ConditionExpression::Arithmetic(ref expr) => write!(f, "{}", expr),
+            ConditionExpression::Case(ref expr) => write!(f, "{}", expr),
+            ConditionExpression::Cast(ref expr) => write!(f, "{}", expr),
         }
     }
 }
@@ -197,24 +214,23 @@ pub fn not_expr(i: &[u8]) -> IResult<&[u8], ConditionExpression> {
     ))(i)
 }
 
+// `IS [NOT] NULL` uses the dedicated `Is`/`IsNot` operators, since unlike `= NULL` it is never
+// unknown; consumers can thus tell the two apart.
 fn is_null(i: &[u8]) -> IResult<&[u8], (Operator, ConditionExpression)> {
-    let (remaining_input, (_, _, not, _, _)) = tuple((
+    let (remaining_input, (_, _, not, _)) = tuple((
         tag_no_case("is"),
-        multispace0,
-        opt(tag_no_case("not")),
-        multispace0,
+        multispace1,
+        opt(terminated(tag_no_case("not"), multispace1)),
         tag_no_case("null"),
     ))(i)?;
 
-    // XXX(malte): bit of a hack; would consumers ever need to know
-    // about "IS NULL" vs. "= NULL"?
     Ok((
         remaining_input,
         (
             if not.is_some() {
-                Operator::NotEqual
+                Operator::IsNot
             } else {
-                Operator::Equal
+                Operator::Is
             },
             ConditionExpression::Base(ConditionBase::Literal(Literal::Null)),
         ),
@@ -226,14 +242,16 @@ fn in_operation(i: &[u8]) -> IResult<&[u8], (Operator, ConditionExpression)> {
         separated_pair(
             opt(terminated(tag_no_case("not"), multispace1)),
             terminated(tag_no_case("in"), multispace0),
-            alt((
-                map(delimited(tag("("), nested_selection, tag(")")), |s| {
-                    ConditionBase::NestedSelect(Box::new(s))
-                }),
-                map(delimited(tag("("), value_list, tag(")")), |vs| {
-                    ConditionBase::LiteralList(vs)
-                }),
-            )),
+            delimited(
+                terminated(tag("("), multispace0),
+                alt((
+                    map(nested_selection, |s| {
+                        ConditionBase::NestedSelect(Box::new(s))
+                    }),
+                    map(value_list, ConditionBase::LiteralList),
+                )),
+                preceded(multispace0, tag(")")),
+            ),
         ),
         |p| {
             let nested = ConditionExpression::Base(p.1);
@@ -246,10 +264,75 @@ fn in_operation(i: &[u8]) -> IResult<&[u8], (Operator, ConditionExpression)> {
     )(i)
 }
 
+// The `AND` separating the bounds belongs to BETWEEN, so each bound is a simple expression
+// rather than a full condition.
+fn between(i: &[u8]) -> IResult<&[u8], (Operator, ConditionExpression)> {
+    let (remaining_input, (not, _, _, lower, _, _, _, upper)) = tuple((
+        opt(terminated(tag_no_case("not"), multispace1)),
+        tag_no_case("between"),
+        multispace1,
+        simple_expr,
+        multispace1,
+        tag_no_case("and"),
+        multispace1,
+        simple_expr,
+    ))(i)?;
+
+    let bounds = ConditionExpression::LogicalOp(ConditionTree {
+        operator: Operator::And,
+        left: Box::new(lower),
+        right: Box::new(upper),
+    });
+    let operator = if not.is_some() {
+        Operator::NotBetween
+    } else {
+        Operator::Between
+    };
+    Ok((remaining_input, (operator, bounds)))
+}
+
+fn like_escape(i: &[u8]) -> IResult<&[u8], char> {
+    map_opt(string_literal, |l| match l {
+        Literal::String(ref s) if s.chars().count() == 1 => s.chars().next(),
+        _ => None,
+    })(i)
+}
+
+// A LIKE against a string pattern, which may be followed by an ESCAPE clause. Other right-hand
+// sides (e.g. placeholders) are handled as ordinary binary comparisons.
+fn like(i: &[u8]) -> IResult<&[u8], (Operator, ConditionExpression)> {
+    let (remaining_input, (not, _, _, pattern, escape)) = tuple((
+        opt(terminated(tag_no_case("not"), multispace1)),
+        tag_no_case("like"),
+        multispace0,
+        map_opt(string_literal, |l| match l {
+            Literal::String(s) => Some(s),
+            _ => None,
+        }),
+        opt(preceded(
+            tuple((multispace1, tag_no_case("escape"), multispace1)),
+            like_escape,
+        )),
+    ))(i)?;
+
+    let operator = if not.is_some() {
+        Operator::NotLike
+    } else {
+        Operator::Like
+    };
+    let pattern = ConditionBase::LikePattern(pattern, escape);
+    Ok((
+        remaining_input,
+        (operator, ConditionExpression::Base(pattern)),
+    ))
+}
+
 fn boolean_primary_rest(i: &[u8]) -> IResult<&[u8], (Operator, ConditionExpression)> {
     alt((
         is_null,
         in_operation,
+        between,
+        like,
         separated_pair(binary_comparison_operator, multispace0, predicate),
     ))(i)
 }
@@ -299,6 +382,7 @@ fn predicate(i: &[u8]) -> IResult<&[u8], ConditionExpression> {
 
 fn simple_expr(i: &[u8]) -> IResult<&[u8], ConditionExpression> {
     alt((
+        map(case_expression, |e| ConditionExpression::Case(Box::new(e))),
         map(
             delimited(
                 terminated(tag("("), multispace0),
@@ -314,6 +398,7 @@ fn simple_expr(i: &[u8]) -> IResult<&[u8], ConditionExpression> {
         map(arithmetic_expression, |e| {
             ConditionExpression::Arithmetic(Box::new(e))
         }),
+        map(cast_expression, |e| ConditionExpression::Cast(Box::new(e))),
         map(literal, |lit| {
             ConditionExpression::Base(ConditionBase::Literal(lit))
         }),
@@ -395,6 +480,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn equality_named_placeholder() {
+        x_equality_variable_placeholder(
+            "foo = :name",
+            Literal::Placeholder(ItemPlaceholder::ColonName(String::from("name"))),
+        );
+    }
+
+    #[test]
+    fn null_safe_equality() {
+        let res = condition_expr(b"a <=> b").unwrap().1;
+        assert_eq!(
+            res,
+            flat_condition_tree(
+                Operator::NullSafeEqual,
+                ConditionBase::Field(Column::from("a")),
+                ConditionBase::Field(Column::from("b"))
+            )
+        );
+        assert_eq!(format!("{}", res), "a <=> b");
+
+        let res = condition_expr(b"a<=>NULL").unwrap().1;
+        assert_eq!(
+            res,
+            flat_condition_tree(
+                Operator::NullSafeEqual,
+                ConditionBase::Field(Column::from("a")),
+                ConditionBase::Literal(Literal::Null)
+            )
+        );
+    }
+
     fn x_equality_variable_placeholder(cond: &str, literal: Literal) {
         let res = condition_expr(cond.as_bytes());
         assert_eq!(
@@ -576,6 +693,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn comparison_operators() {
+        let cases = [
+            ("a = 1", Operator::Equal),
+            ("a <> 2", Operator::NotEqual),
+            ("a != 2", Operator::NotEqual),
+            ("a > 3", Operator::Greater),
+            ("a < 3", Operator::Less),
+            ("a >= 4", Operator::GreaterOrEqual),
+            ("a<=4", Operator::LessOrEqual),
+        ];
+        for &(cond, ref op) in cases.iter() {
+            let res = condition_expr(cond.as_bytes()).unwrap().1;
+            match res {
+                ConditionExpression::ComparisonOp(ref ct) => assert_eq!(ct.operator, *op),
+                _ => panic!("{} did not parse as a comparison", cond),
+            }
+        }
+
+        let res = condition_expr(b"a > b");
+        assert_eq!(
+            res.unwrap().1,
+            flat_condition_tree(
+                Operator::Greater,
+                ConditionBase::Field(Column::from("a")),
+                ConditionBase::Field(Column::from("b"))
+            )
+        );
+    }
+
     #[test]
     fn empty_string_literal() {
         let cond = "foo = ''";
@@ -679,6 +826,65 @@ mod tests {
         assert_eq!(res.unwrap().1, complete);
     }
 
+    #[test]
+    fn mixed_and_or_with_parentheses() {
+        use common::Literal;
+        use ConditionBase::*;
+        use ConditionExpression::*;
+
+        let eq = |col: &str, val: i64| {
+            ComparisonOp(ConditionTree {
+                operator: Operator::Equal,
+                left: Box::new(Base(Field(col.into()))),
+                right: Box::new(Base(Literal(Literal::Integer(val)))),
+            })
+        };
+        let logical = |operator: Operator, left, right| {
+            LogicalOp(ConditionTree {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+        };
+
+        // explicit grouping on the right-hand side of AND
+        let res = condition_expr(b"a = 1 AND (b = 2 OR c = 3)");
+        assert_eq!(
+            res.unwrap().1,
+            logical(
+                Operator::And,
+                eq("a", 1),
+                Bracketed(Box::new(logical(Operator::Or, eq("b", 2), eq("c", 3)))),
+            )
+        );
+
+        // AND binds tighter than OR, regardless of position
+        let res = condition_expr(b"a = 1 OR b = 2 AND c = 3");
+        assert_eq!(
+            res.unwrap().1,
+            logical(
+                Operator::Or,
+                eq("a", 1),
+                logical(Operator::And, eq("b", 2), eq("c", 3)),
+            )
+        );
+
+        // NOT applied to a parenthesized group
+        let res = condition_expr(b"NOT (a = 1 OR b = 2) AND c = 3");
+        assert_eq!(
+            res.unwrap().1,
+            logical(
+                Operator::And,
+                NegationOp(Box::new(Bracketed(Box::new(logical(
+                    Operator::Or,
+                    eq("a", 1),
+                    eq("b", 2),
+                ))))),
+                eq("c", 3),
+            )
+        );
+    }
+
     #[test]
     fn negation() {
         let cond = "not bar = 12 or foobar = 'a'";
@@ -829,6 +1035,132 @@ mod tests {
         assert_eq!(res.unwrap().1, expected);
     }
 
+    #[test]
+    fn in_string_list_and_subquery() {
+        use select::SelectStatement;
+        use table::Table;
+        use ConditionBase::*;
+
+        let cond = "status IN ( 'active', 'pending' )";
+        let res = condition_expr(cond.as_bytes());
+        assert_eq!(
+            res.unwrap().1,
+            flat_condition_tree(
+                Operator::In,
+                Field("status".into()),
+                LiteralList(vec!["active".into(), "pending".into()]),
+            )
+        );
+
+        let cond = "id NOT IN ( SELECT user_id FROM banned )";
+        let res = condition_expr(cond.as_bytes());
+        let nested_select = Box::new(SelectStatement {
+            tables: vec![Table::from("banned")],
+            fields: columns(&["user_id"]),
+            ..Default::default()
+        });
+        assert_eq!(
+            res.unwrap().1,
+            flat_condition_tree(
+                Operator::NotIn,
+                Field("id".into()),
+                NestedSelect(nested_select),
+            )
+        );
+    }
+
+    #[test]
+    fn between_and_not_between() {
+        use common::Literal;
+        use ConditionBase::*;
+
+        let bounds = |lower, upper| {
+            Box::new(ConditionExpression::LogicalOp(ConditionTree {
+                operator: Operator::And,
+                left: Box::new(ConditionExpression::Base(Literal(Literal::Integer(lower)))),
+                right: Box::new(ConditionExpression::Base(Literal(Literal::Integer(upper)))),
+            }))
+        };
+
+        let cond = "price BETWEEN 10 AND 20";
+        let res = condition_expr(cond.as_bytes());
+        let expected = ConditionExpression::ComparisonOp(ConditionTree {
+            operator: Operator::Between,
+            left: Box::new(ConditionExpression::Base(Field("price".into()))),
+            right: bounds(10, 20),
+        });
+        assert_eq!(res.unwrap().1, expected);
+        assert_eq!(format!("{}", expected), cond);
+
+        // the bounds' AND binds tighter than a logical AND
+        let cond = "price NOT BETWEEN 1 AND 5 AND qty = 3";
+        let res = condition_expr(cond.as_bytes());
+        let expected = ConditionExpression::LogicalOp(ConditionTree {
+            operator: Operator::And,
+            left: Box::new(ConditionExpression::ComparisonOp(ConditionTree {
+                operator: Operator::NotBetween,
+                left: Box::new(ConditionExpression::Base(Field("price".into()))),
+                right: bounds(1, 5),
+            })),
+            right: Box::new(flat_condition_tree(
+                Operator::Equal,
+                Field("qty".into()),
+                Literal(Literal::Integer(3)),
+            )),
+        });
+        assert_eq!(res.unwrap().1, expected);
+        assert_eq!(format!("{}", expected), cond);
+    }
+
+    #[test]
+    fn like_and_not_like() {
+        use ConditionBase::*;
+
+        let cond = "name LIKE 'a%'";
+        let res = condition_expr(cond.as_bytes());
+        let expected = flat_condition_tree(
+            Operator::Like,
+            Field("name".into()),
+            LikePattern("a%".into(), None),
+        );
+        assert_eq!(res.unwrap().1, expected);
+        assert_eq!(format!("{}", expected), cond);
+
+        let cond = "name NOT LIKE 'a%'";
+        let res = condition_expr(cond.as_bytes());
+        let expected = flat_condition_tree(
+            Operator::NotLike,
+            Field("name".into()),
+            LikePattern("a%".into(), None),
+        );
+        assert_eq!(res.unwrap().1, expected);
+        assert_eq!(format!("{}", expected), cond);
+    }
+
+    #[test]
+    fn like_with_escape() {
+        use ConditionBase::*;
+
+        let cond = "name LIKE 'a\\\\%' ESCAPE '\\\\'";
+        let res = condition_expr(cond.as_bytes());
+        let expected = flat_condition_tree(
+            Operator::Like,
+            Field("name".into()),
+            LikePattern("a\\%".into(), Some('\\')),
+        );
+        assert_eq!(res.unwrap().1, expected);
+
+        let cond = "name LIKE '10!%' ESCAPE '!'";
+        let res = condition_expr(cond.as_bytes());
+        let expected = flat_condition_tree(
+            Operator::Like,
+            Field("name".into()),
+            LikePattern("10!%".into(), Some('!')),
+        );
+        assert_eq!(res.unwrap().1, expected);
+        assert_eq!(format!("{}", expected), cond);
+    }
+
     #[test]
     fn is_null() {
         use common::Literal;
@@ -838,17 +1170,22 @@ mod tests {
 
         let res = condition_expr(cond.as_bytes());
         let expected =
-            flat_condition_tree(Operator::Equal, Field("bar".into()), Literal(Literal::Null));
+            flat_condition_tree(Operator::Is, Field("bar".into()), Literal(Literal::Null));
         assert_eq!(res.unwrap().1, expected);
+        assert_eq!(format!("{}", expected), cond);
 
         let cond = "bar IS NOT NULL";
 
         let res = condition_expr(cond.as_bytes());
-        let expected = flat_condition_tree(
-            Operator::NotEqual,
-            Field("bar".into()),
-            Literal(Literal::Null),
-        );
+        let expected =
+            flat_condition_tree(Operator::IsNot, Field("bar".into()), Literal(Literal::Null));
+        assert_eq!(res.unwrap().1, expected);
+        assert_eq!(format!("{}", expected), cond);
+
+        // distinct from a (never true) equality comparison with NULL
+        let res = condition_expr(b"bar = NULL");
+        let expected =
+            flat_condition_tree(Operator::Equal, Field("bar".into()), Literal(Literal::Null));
         assert_eq!(res.unwrap().1, expected);
     }
 
@@ -903,7 +1240,7 @@ mod tests {
                                     ConditionExpression::LogicalOp(ConditionTree {
                                         operator: Operator::And,
                                         left: Box::new(flat_condition_tree(
-                                            Operator::Equal,
+                                            Operator::Is,
                                             Field("parent_comments.user_id".into()),
                                             Literal(Literal::Null),
                                         )),
@@ -922,7 +1259,7 @@ mod tests {
                                 ConditionExpression::LogicalOp(ConditionTree {
                                     operator: Operator::Or,
                                     left: Box::new(flat_condition_tree(
-                                        Operator::Equal,
+                                        Operator::Is,
                                         Field("parent_comments.id".into()),
                                         Literal(Literal::Null),
                                     )),
@@ -0,0 +1,70 @@
+use std::cell::RefCell;
+use std::mem;
+
+use dialect::Dialect;
+use warning::Warning;
+
+// The settings and side results of the parse in progress on this thread, set up by the `parse_*`
+// entry points and read by the parsers that need them.
+pub(crate) struct Context {
+    // The dialect being parsed, or `None` in the default permissive mode.
+    pub dialect: Option<Dialect>,
+    // Start address of the input being parsed, while recording spans.
+    pub span_start: Option<usize>,
+    // Start address of the input being parsed and the warnings so far, while collecting warnings.
+    pub warnings: Option<(usize, Vec<Warning>)>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Context> = const {
+        RefCell::new(Context {
+            dialect: None,
+            span_start: None,
+            warnings: None,
+        })
+    };
+}
+
+/// Runs `f` on this thread's parse context. `f` must not call back into this module.
+pub(crate) fn with<T, F: FnOnce(&mut Context) -> T>(f: F) -> T {
+    CONTEXT.with(|c| f(&mut c.borrow_mut()))
+}
+
+// Puts back the previous value of a context field when dropped, including when parsing panics.
+struct Restore<V> {
+    field: fn(&mut Context) -> &mut V,
+    previous: Option<V>,
+}
+
+impl<V> Restore<V> {
+    // Puts back the previous value, if not done already, returning the value it replaces.
+    fn restore(&mut self) -> Option<V> {
+        let field = self.field;
+        self.previous
+            .take()
+            .map(|previous| with(|c| mem::replace(field(c), previous)))
+    }
+}
+
+impl<V> Drop for Restore<V> {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+/// Runs `f` with the context field chosen by `field` set to `value`, then restores its previous
+/// value. Returns what `f` returned along with the value the field was left with.
+pub(crate) fn scoped<V, T, F: FnOnce() -> T>(
+    field: fn(&mut Context) -> &mut V,
+    value: V,
+    f: F,
+) -> (T, V) {
+    let previous = with(|c| mem::replace(field(c), value));
+    let mut restore = Restore {
+        field,
+        previous: Some(previous),
+    };
+    let result = f();
+    let last = restore.restore().unwrap();
+    (result, last)
+}
@@ -1,31 +1,490 @@
 use nom::character::complete::{digit1, multispace0, multispace1};
 use std::fmt;
 use std::str;
-use std::str::FromStr;
 
-use column::{Column, ColumnConstraint, ColumnSpecification};
+use alter::{AlterOperation, AlterTableStatement};
+use column::{Column, ColumnConstraint, ColumnSpecification, IndexColumn};
 use common::{
-    column_identifier_no_alias, parse_comment, sql_identifier, statement_terminator,
-    schema_table_reference, type_identifier, ws_sep_comma, Literal, Real, SqlType, TableKey,
+    bit_string_literal, blob_literal, charset_keyword, column_identifier_no_alias,
+    column_identifier_no_function, delim_u16, numeric_literal, parse_comment, qualified_table_name,
+    raw_parenthesized_expr, schema_table_reference, sql_identifier, statement_terminator,
+    string_literal, type_identifier, ws_sep_comma, ws_sep_equals, ForeignKeyReference, IndexType,
+    Literal, ReferentialAction, SqlType, TableKey,
 };
 use compound_select::{compound_selection, CompoundSelectStatement};
-use create_table_options::table_options;
+use create_table_options::{table_options, TableOption};
+use dialect::{self, Dialect};
 use keywords::escape_if_keyword;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, tag_no_case, take_until};
-use nom::combinator::{map, opt};
-use nom::multi::{many0, many1};
+use nom::bytes::complete::{is_not, tag, tag_no_case};
+use nom::combinator::{map, map_res, opt, verify};
+use nom::error::{ErrorKind, ParseError};
+use nom::multi::{many0, separated_list1};
 use nom::sequence::{delimited, preceded, terminated, tuple};
 use nom::IResult;
-use order::{order_type, OrderType};
+use order::order_type;
 use select::{nested_selection, SelectStatement};
+use span::{self, Span};
 use table::Table;
+use warning;
+
+/// Returned when generated columns depend on each other in a cycle. Holds the names of the
+/// columns that could not be ordered, in declaration order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CycleError {
+    pub columns: Vec<String>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cyclic generated column dependencies among {}",
+            self.columns.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for CycleError {}
 
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct CreateTableStatement {
     pub table: Table,
     pub fields: Vec<ColumnSpecification>,
     pub keys: Option<Vec<TableKey>>,
+    /// Table options following the column definitions, such as `AUTO_INCREMENT=1000`.
+    pub options: Vec<TableOption>,
+    /// MariaDB's `WITH SYSTEM VERSIONING`, which keeps the history of every row.
+    pub system_versioning: bool,
+    /// SQLite's `WITHOUT ROWID`, which stores the table clustered on its primary key.
+    pub without_rowid: bool,
+    /// SQLite's `STRICT`, which enforces the declared column types.
+    pub strict: bool,
+    /// Raw text following `PARTITION BY`; partitioning is captured but not modeled.
+    pub partition: Option<String>,
+    /// Where the statement is in the parsed input, not including its terminator; only recorded
+    /// by `parse_query_with_spans`.
+    pub span: Option<Span>,
+}
+
+impl CreateTableStatement {
+    /// Returns true if any part of the statement was captured as raw text rather than parsed into
+    /// the AST, i.e. if the statement cannot be fully inspected or reproduced from its structure.
+    pub fn has_unsupported_features(&self) -> bool {
+        self.partition.is_some()
+            || self.fields.iter().any(|field| {
+                field.constraints.iter().any(|c| {
+                    matches!(
+                        *c,
+                        ColumnConstraint::DefaultExpression(_) | ColumnConstraint::Generated(..)
+                    )
+                })
+            })
+    }
+
+    /// Returns true if both tables declare the same columns, in the same order, ignoring their
+    /// keys and table options. Columns are compared by name, type and constraints only, so the
+    /// table name they are qualified with and their comments do not matter.
+    pub fn columns_equal(&self, other: &CreateTableStatement) -> bool {
+        self.fields.len() == other.fields.len()
+            && self.fields.iter().zip(&other.fields).all(|(a, b)| {
+                a.column.name == b.column.name
+                    && a.sql_type == b.sql_type
+                    && a.constraints == b.constraints
+            })
+    }
+
+    /// Returns true if both tables declare the same keys, in any order, ignoring their columns.
+    /// A key declared twice must be declared twice in both. Declaring no keys at all is the same
+    /// as an empty key list.
+    pub fn keys_equal(&self, other: &CreateTableStatement) -> bool {
+        let keys = self.keys.as_deref().unwrap_or_default();
+        let other_keys = other.keys.as_deref().unwrap_or_default();
+        let count = |keys: &[TableKey], key: &TableKey| keys.iter().filter(|&k| k == key).count();
+        keys.len() == other_keys.len()
+            && keys.iter().all(|k| count(keys, k) == count(other_keys, k))
+    }
+
+    /// Multiplies the declared length of every `CHAR`, `VARCHAR`, `BINARY` and `VARBINARY` column
+    /// by `factor`, rounding to the nearest integer and clamping to `0..=u16::MAX`.
+    pub fn scale_string_lengths(&mut self, factor: f64) {
+        let scale = |len: u16| {
+            (f64::from(len) * factor)
+                .round()
+                .max(0.0)
+                .min(f64::from(u16::MAX)) as u16
+        };
+        for field in self.fields.iter_mut() {
+            field.sql_type = match field.sql_type {
+                SqlType::Char(len) => SqlType::Char(scale(len)),
+                SqlType::Varchar(len) => SqlType::Varchar(scale(len)),
+                SqlType::Binary(len) => SqlType::Binary(scale(len)),
+                SqlType::Varbinary(len) => SqlType::Varbinary(scale(len)),
+                ref other => other.clone(),
+            };
+        }
+    }
+
+    /// Rewrites column definitions into a canonical form, so that tables written differently but
+    /// with the same meaning compare equal. Type keywords are already case-insensitive when
+    /// parsed, and are always displayed in upper case; in addition, this considers equivalent:
+    ///
+    /// - integer types of any display width, or none, which does not affect the range of values
+    ///   (widths are removed, so `INT(11)` becomes `INT`);
+    /// - `BOOL` and `TINYINT` of any width;
+    /// - character set and collation names that differ only in case;
+    /// - `DEFAULT NULL` and no default on a column that is not `NOT NULL`.
+    ///
+    /// Names, column order, key order and everything else are left alone.
+    pub fn normalize(&mut self) {
+        for field in self.fields.iter_mut() {
+            field.sql_type = match field.sql_type {
+                SqlType::Bool | SqlType::Tinyint(_) => SqlType::Tinyint(None),
+                SqlType::UnsignedTinyint(_) => SqlType::UnsignedTinyint(None),
+                SqlType::Int(_) => SqlType::Int(None),
+                SqlType::UnsignedInt(_) => SqlType::UnsignedInt(None),
+                SqlType::Bigint(_) => SqlType::Bigint(None),
+                SqlType::UnsignedBigint(_) => SqlType::UnsignedBigint(None),
+                ref other => other.clone(),
+            };
+
+            let nullable = !field.constraints.contains(&ColumnConstraint::NotNull);
+            field.constraints.retain(|c| match *c {
+                ColumnConstraint::DefaultValue(Literal::Null) => !nullable,
+                _ => true,
+            });
+            for constraint in field.constraints.iter_mut() {
+                match *constraint {
+                    ColumnConstraint::CharacterSet(ref mut name)
+                    | ColumnConstraint::Collation(ref mut name) => name.make_ascii_lowercase(),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    /// Moves every key except primary and foreign keys out of the table definition and into a separate
+    /// `CREATE INDEX` statement. Unnamed keys are named after the table and their columns. `USING`
+    /// index methods are dropped.
+    pub fn with_separate_indexes(mut self) -> (Self, Vec<CreateIndexStatement>) {
+        let mut keys = vec![];
+        let mut indexes = vec![];
+        for key in self.keys.take().unwrap_or_default() {
+            let (name, columns, comment, unique, fulltext) = match key {
+                TableKey::PrimaryKey { .. } | TableKey::ForeignKey { .. } => {
+                    keys.push(key);
+                    continue;
+                }
+                TableKey::UniqueKey {
+                    name,
+                    columns,
+                    comment,
+                    ..
+                } => (name, columns, comment, true, false),
+                TableKey::FulltextKey {
+                    name,
+                    columns,
+                    comment,
+                } => (name, columns, comment, false, true),
+                TableKey::Key {
+                    name,
+                    columns,
+                    comment,
+                    ..
+                } => (Some(name), columns, comment, false, false),
+            };
+            let name = name.unwrap_or_else(|| {
+                let column_names: Vec<_> = columns.iter().map(|c| c.column.name.as_str()).collect();
+                format!("{}_{}_idx", self.table.name, column_names.join("_"))
+            });
+            indexes.push(CreateIndexStatement {
+                name,
+                table: self.table.clone(),
+                columns,
+                unique,
+                fulltext,
+                nulls_not_distinct: false,
+                comment,
+            });
+        }
+        if !keys.is_empty() {
+            self.keys = Some(keys);
+        }
+        (self, indexes)
+    }
+
+    /// Returns the columns ordered so that every generated column comes after the columns its
+    /// expression refers to, keeping declaration order where possible. References to columns not
+    /// in this table are ignored.
+    pub fn columns_in_generation_order(&self) -> Result<Vec<&ColumnSpecification>, CycleError> {
+        let dependencies: Vec<Vec<usize>> = self
+            .fields
+            .iter()
+            .map(|field| {
+                field
+                    .generation_dependencies()
+                    .iter()
+                    .filter_map(|dep| {
+                        self.fields
+                            .iter()
+                            .position(|f| f.column.name.eq_ignore_ascii_case(dep))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut placed = vec![false; self.fields.len()];
+        let mut ordered = Vec::with_capacity(self.fields.len());
+        while ordered.len() < self.fields.len() {
+            let next = (0..self.fields.len())
+                .find(|&i| !placed[i] && dependencies[i].iter().all(|&d| placed[d]));
+            match next {
+                Some(i) => {
+                    placed[i] = true;
+                    ordered.push(&self.fields[i]);
+                }
+                None => {
+                    let columns = self
+                        .fields
+                        .iter()
+                        .zip(placed)
+                        .filter(|&(_, placed)| !placed)
+                        .map(|(field, _)| field.column.name.clone())
+                        .collect();
+                    return Err(CycleError { columns });
+                }
+            }
+        }
+        Ok(ordered)
+    }
+
+    /// Returns the column set of every key that enforces uniqueness: the primary key and unique
+    /// keys, followed by columns declared `PRIMARY KEY` or `UNIQUE` inline that no key already
+    /// covers on its own.
+    pub fn unique_column_sets(&self) -> Vec<Vec<&Column>> {
+        let mut sets: Vec<Vec<&Column>> = self
+            .keys
+            .iter()
+            .flatten()
+            .filter_map(|key| match *key {
+                TableKey::PrimaryKey { ref columns, .. }
+                | TableKey::UniqueKey { ref columns, .. } => {
+                    Some(columns.iter().map(|c| &c.column).collect())
+                }
+                _ => None,
+            })
+            .collect();
+        for field in &self.fields {
+            let unique = field
+                .constraints
+                .iter()
+                .any(|c| matches!(*c, ColumnConstraint::PrimaryKey | ColumnConstraint::Unique));
+            if unique && !sets.contains(&vec![&field.column]) {
+                sets.push(vec![&field.column]);
+            }
+        }
+        sets
+    }
+
+    /// Returns the tables this table's foreign keys reference, inline `REFERENCES` constraints
+    /// included, in declaration order and without duplicates. A self-reference lists the table
+    /// itself.
+    pub fn foreign_key_dependencies(&self) -> Vec<&Table> {
+        let mut tables: Vec<&Table> = vec![];
+        for key in self.keys.iter().flatten() {
+            if let TableKey::ForeignKey { ref reference, .. } = *key {
+                if !tables.contains(&&reference.table) {
+                    tables.push(&reference.table);
+                }
+            }
+        }
+        tables
+    }
+
+    // Returns true if `key` only mirrors `PRIMARY KEY` or `REFERENCES` constraints declared inline
+    // on its columns, as the parser records them among the keys too.
+    pub(crate) fn mirrors_inline_constraint(&self, key: &TableKey) -> bool {
+        let declared_inline = |column: &Column, constraint: &ColumnConstraint| {
+            self.fields
+                .iter()
+                .any(|f| f.column == *column && f.constraints.contains(constraint))
+        };
+        match *key {
+            TableKey::PrimaryKey { ref columns, .. } => columns.iter().all(|c| {
+                *c == IndexColumn::from(c.column.clone())
+                    && declared_inline(&c.column, &ColumnConstraint::PrimaryKey)
+            }),
+            TableKey::ForeignKey {
+                name: None,
+                index_name: None,
+                ref columns,
+                ref reference,
+            } => {
+                columns.len() == 1
+                    && declared_inline(
+                        &columns[0],
+                        &ColumnConstraint::References(reference.clone()),
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns one `ALTER TABLE ... ADD <key>` statement per key of this table, for engines that
+    /// require keys to be added after the table is created.
+    pub fn keys_as_alter_adds(&self) -> Vec<AlterTableStatement> {
+        self.keys
+            .iter()
+            .flatten()
+            .map(|key| AlterTableStatement {
+                table: self.table.clone(),
+                operations: vec![AlterOperation::AddKey(key.clone())],
+            })
+            .collect()
+    }
+
+    /// Folds `CREATE INDEX` statements on this table back into its key definitions. Indexes on
+    /// other tables are ignored. `NULLS NOT DISTINCT` has no inline form, so it is dropped.
+    pub fn with_inline_keys(mut self, indexes: Vec<CreateIndexStatement>) -> Self {
+        let mut keys = self.keys.take().unwrap_or_default();
+        for index in indexes {
+            if index.table.name != self.table.name {
+                continue;
+            }
+            keys.push(if index.unique {
+                TableKey::UniqueKey {
+                    name: Some(index.name),
+                    columns: index.columns,
+                    comment: index.comment,
+                    index_type: None,
+                }
+            } else if index.fulltext {
+                TableKey::FulltextKey {
+                    name: Some(index.name),
+                    columns: index.columns,
+                    comment: index.comment,
+                }
+            } else {
+                TableKey::Key {
+                    name: index.name,
+                    columns: index.columns,
+                    comment: index.comment,
+                    index_type: None,
+                }
+            });
+        }
+        if !keys.is_empty() {
+            self.keys = Some(keys);
+        }
+        self
+    }
+}
+
+/// Builds a `CreateTableStatement` column by column, for generating DDL. Column-level methods such
+/// as `not_null` apply to the most recently added column, and panic if there is none.
+#[derive(Clone, Debug)]
+pub struct CreateTableBuilder {
+    statement: CreateTableStatement,
+}
+
+impl CreateTableBuilder {
+    pub fn new(table: &str) -> Self {
+        CreateTableBuilder {
+            statement: CreateTableStatement {
+                table: Table::from(table),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn column(mut self, name: &str, sql_type: SqlType) -> Self {
+        let column = self.table_column(name);
+        self.statement
+            .fields
+            .push(ColumnSpecification::new(column, sql_type));
+        self
+    }
+
+    pub fn constraint(mut self, constraint: ColumnConstraint) -> Self {
+        self.last_column().constraints.push(constraint);
+        self
+    }
+
+    pub fn not_null(self) -> Self {
+        self.constraint(ColumnConstraint::NotNull)
+    }
+
+    pub fn default_value<L: Into<Literal>>(self, value: L) -> Self {
+        self.constraint(ColumnConstraint::DefaultValue(value.into()))
+    }
+
+    pub fn auto_increment(self) -> Self {
+        self.constraint(ColumnConstraint::AutoIncrement)
+    }
+
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.last_column().comment = Some(String::from(comment));
+        self
+    }
+
+    pub fn primary_key(self, columns: &[&str]) -> Self {
+        let columns = self.table_columns(columns);
+        self.key(TableKey::PrimaryKey {
+            columns,
+            index_type: None,
+        })
+    }
+
+    pub fn unique_key(self, name: &str, columns: &[&str]) -> Self {
+        let columns = self.table_columns(columns);
+        self.key(TableKey::UniqueKey {
+            name: Some(String::from(name)),
+            columns,
+            comment: None,
+            index_type: None,
+        })
+    }
+
+    pub fn index(self, name: &str, columns: &[&str]) -> Self {
+        let columns = self.table_columns(columns);
+        self.key(TableKey::Key {
+            name: String::from(name),
+            columns,
+            comment: None,
+            index_type: None,
+        })
+    }
+
+    pub fn build(self) -> CreateTableStatement {
+        self.statement
+    }
+
+    fn key(mut self, key: TableKey) -> Self {
+        self.statement.keys.get_or_insert_with(Vec::new).push(key);
+        self
+    }
+
+    fn last_column(&mut self) -> &mut ColumnSpecification {
+        self.statement
+            .fields
+            .last_mut()
+            .expect("no column has been added to the table yet")
+    }
+
+    // Columns are qualified with the table name, as they are when parsed.
+    fn table_column(&self, name: &str) -> Column {
+        Column {
+            table: Some(self.statement.table.name.clone()),
+            ..Column::from(name)
+        }
+    }
+
+    fn table_columns(&self, names: &[&str]) -> Vec<IndexColumn> {
+        names
+            .iter()
+            .map(|name| IndexColumn::from(self.table_column(name)))
+            .collect()
+    }
 }
 
 impl fmt::Display for CreateTableStatement {
@@ -41,17 +500,94 @@ impl fmt::Display for CreateTableStatement {
                 .collect::<Vec<_>>()
                 .join(", ")
         )?;
-        if let Some(ref keys) = self.keys {
-            write!(
-                f,
-                ", {}",
-                keys.iter()
-                    .map(|key| format!("{}", key))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )?;
+        // primary and foreign keys already declared inline on their columns aren't repeated
+        for key in self.keys.iter().flatten() {
+            if !self.mirrors_inline_constraint(key) {
+                write!(f, ", {}", key)?;
+            }
+        }
+        write!(f, ")")?;
+        for option in &self.options {
+            write!(f, " {}", option)?;
+        }
+        if self.system_versioning {
+            write!(f, " WITH SYSTEM VERSIONING")?;
+        }
+        if self.without_rowid {
+            write!(f, " WITHOUT ROWID")?;
+        }
+        if self.strict {
+            write!(f, "{} STRICT", if self.without_rowid { "," } else { "" })?;
+        }
+        if let Some(ref partition) = self.partition {
+            write!(f, " PARTITION BY {}", partition)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct CreateIndexStatement {
+    pub name: String,
+    pub table: Table,
+    pub columns: Vec<IndexColumn>,
+    pub unique: bool,
+    pub fulltext: bool,
+    /// Postgres' `NULLS NOT DISTINCT`: a unique index that treats NULLs as equal to each other.
+    pub nulls_not_distinct: bool,
+    pub comment: Option<String>,
+}
+
+impl fmt::Display for CreateIndexStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CREATE ")?;
+        if self.unique {
+            write!(f, "UNIQUE ")?;
+        } else if self.fulltext {
+            write!(f, "FULLTEXT ")?;
+        }
+        write!(
+            f,
+            "INDEX {} ON {} ",
+            escape_if_keyword(&self.name),
+            self.table
+        )?;
+        write!(
+            f,
+            "({})",
+            self.columns
+                .iter()
+                .map(|c| format!("{}", c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        if self.nulls_not_distinct {
+            write!(f, " NULLS NOT DISTINCT")?;
+        }
+        if let Some(ref comment) = self.comment {
+            write!(f, " COMMENT {}", Literal::from(comment.as_str()))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct CreateDatabaseStatement {
+    pub name: String,
+    pub charset: Option<String>,
+    pub collation: Option<String>,
+}
+
+impl fmt::Display for CreateDatabaseStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CREATE DATABASE {}", escape_if_keyword(&self.name))?;
+        if let Some(ref charset) = self.charset {
+            write!(f, " DEFAULT CHARACTER SET {}", charset)?;
+        }
+        if let Some(ref collation) = self.collation {
+            write!(f, " DEFAULT COLLATE {}", collation)?;
         }
-        write!(f, ")")
+        Ok(())
     }
 }
 
@@ -70,11 +606,29 @@ impl fmt::Display for SelectSpecification {
     }
 }
 
+/// The scope of a view's `WITH CHECK OPTION`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum ViewCheckOption {
+    Cascaded,
+    Local,
+}
+
+impl fmt::Display for ViewCheckOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ViewCheckOption::Cascaded => write!(f, "CASCADED"),
+            ViewCheckOption::Local => write!(f, "LOCAL"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct CreateViewStatement {
     pub name: String,
     pub fields: Vec<Column>,
     pub definition: Box<SelectSpecification>,
+    /// `WITH [CASCADED | LOCAL] CHECK OPTION`; a bare `WITH CHECK OPTION` is `CASCADED`.
+    pub check_option: Option<ViewCheckOption>,
 }
 
 impl fmt::Display for CreateViewStatement {
@@ -94,38 +648,104 @@ impl fmt::Display for CreateViewStatement {
             write!(f, ") ")?;
         }
         write!(f, "AS ")?;
-        write!(f, "{}", self.definition)
+        write!(f, "{}", self.definition)?;
+        if let Some(check_option) = self.check_option {
+            write!(f, " WITH {} CHECK OPTION", check_option)?;
+        }
+        Ok(())
     }
 }
 
-// MySQL grammar element for index column definition (§13.1.18, index_col_name)
-pub fn index_col_name(i: &[u8]) -> IResult<&[u8], (Column, Option<u16>, Option<OrderType>)> {
-    let (remaining_input, (column, len_u8, order)) = tuple((
-        terminated(column_identifier_no_alias, multispace0),
-        opt(delimited(tag("("), digit1, tag(")"))),
+// MySQL grammar element for index column definition (§13.1.18, index_col_name), extended with
+// the per-column `COLLATE name` that SQLite allows.
+pub fn index_col_name(i: &[u8]) -> IResult<&[u8], IndexColumn> {
+    let (remaining_input, (column, length, collation, order)) = tuple((
+        terminated(column_identifier_no_function, multispace0),
+        opt(terminated(delim_u16, multispace0)),
+        opt(delimited(
+            terminated(tag_no_case("collate"), multispace1),
+            sql_identifier,
+            multispace0,
+        )),
         opt(order_type),
     ))(i)?;
-    let len = len_u8.map(|l| u16::from_str(str::from_utf8(l).unwrap()).unwrap());
 
-    Ok((remaining_input, (column, len, order)))
+    let collation = collation.map(|c| String::from_utf8(c.to_vec()).unwrap());
+    Ok((
+        remaining_input,
+        IndexColumn {
+            column,
+            length,
+            collation,
+            order,
+        },
+    ))
 }
 
 // Helper for list of index columns
-pub fn index_col_list(i: &[u8]) -> IResult<&[u8], Vec<Column>> {
-    many0(map(
-        terminated(index_col_name, opt(ws_sep_comma)),
-        // XXX(malte): ignores length and order
-        |e| e.0,
+pub fn index_col_list(i: &[u8]) -> IResult<&[u8], Vec<IndexColumn>> {
+    many0(terminated(index_col_name, opt(ws_sep_comma)))(i)
+}
+
+// Helper for the list of columns of a foreign key or of the key it references, which are plain
+// column names.
+fn key_col_list(i: &[u8]) -> IResult<&[u8], Vec<Column>> {
+    many0(terminated(
+        terminated(column_identifier_no_function, multispace0),
+        opt(ws_sep_comma),
     ))(i)
 }
 
 // Parse rule for an individual key specification.
 pub fn key_specification(i: &[u8]) -> IResult<&[u8], TableKey> {
-    alt((full_text_key, primary_key, unique, key_or_index))(i)
+    alt((
+        warning::discard_on_failure(full_text_key),
+        warning::discard_on_failure(primary_key),
+        warning::discard_on_failure(unique),
+        warning::discard_on_failure(key_or_index),
+        warning::discard_on_failure(foreign_key),
+    ))(i)
+}
+
+// Parse rule for a table-level foreign key:
+// `[CONSTRAINT [symbol]] FOREIGN KEY [index_name] (column, ...) REFERENCES ...`.
+fn foreign_key(i: &[u8]) -> IResult<&[u8], TableKey> {
+    let (remaining_input, (constraint, _, _, _, index_name, _, columns, _, reference)) = tuple((
+        opt(preceded(
+            terminated(tag_no_case("constraint"), multispace1),
+            opt(terminated(sql_identifier, multispace1)),
+        )),
+        tag_no_case("foreign"),
+        multispace1,
+        tag_no_case("key"),
+        opt(preceded(multispace1, sql_identifier)),
+        multispace0,
+        delimited(
+            tag("("),
+            delimited(multispace0, key_col_list, multispace0),
+            tag(")"),
+        ),
+        multispace0,
+        foreign_key_reference,
+    ))(i)?;
+
+    let name = constraint
+        .and_then(|symbol| symbol)
+        .map(|n| String::from_utf8(n.to_vec()).unwrap());
+    let index_name = index_name.map(|n| String::from_utf8(n.to_vec()).unwrap());
+    Ok((
+        remaining_input,
+        TableKey::ForeignKey {
+            name,
+            index_name,
+            columns,
+            reference,
+        },
+    ))
 }
 
 fn full_text_key(i: &[u8]) -> IResult<&[u8], TableKey> {
-    let (remaining_input, (_, _, _, _, name, _, columns)) = tuple((
+    let (remaining_input, (_, _, _, _, name, _, columns, comment)) = tuple((
         tag_no_case("fulltext"),
         multispace1,
         alt((tag_no_case("key"), tag_no_case("index"))),
@@ -137,38 +757,91 @@ fn full_text_key(i: &[u8]) -> IResult<&[u8], TableKey> {
             delimited(multispace0, index_col_list, multispace0),
             tag(")"),
         ),
+        opt(parse_comment),
     ))(i)?;
 
     match name {
         Some(name) => {
             let n = String::from_utf8(name.to_vec()).unwrap();
-            Ok((remaining_input, TableKey::FulltextKey(Some(n), columns)))
+            Ok((
+                remaining_input,
+                TableKey::FulltextKey {
+                    name: Some(n),
+                    columns,
+                    comment,
+                },
+            ))
         }
-        None => Ok((remaining_input, TableKey::FulltextKey(None, columns))),
+        None => Ok((
+            remaining_input,
+            TableKey::FulltextKey {
+                name: None,
+                columns,
+                comment,
+            },
+        )),
     }
 }
 
+// Parse rule for an index method, `USING BTREE` or `USING HASH`.
+fn index_type(i: &[u8]) -> IResult<&[u8], IndexType> {
+    preceded(
+        terminated(tag_no_case("using"), multispace1),
+        alt((
+            map(tag_no_case("btree"), |_| IndexType::BTree),
+            map(tag_no_case("hash"), |_| IndexType::Hash),
+        )),
+    )(i)
+}
+
+// Parse rule for a key's column list and index method, which may be given with `USING` either
+// before or after the columns, but not in both places.
+fn index_columns(i: &[u8]) -> IResult<&[u8], (Vec<IndexColumn>, Option<IndexType>)> {
+    map(
+        verify(
+            tuple((
+                opt(terminated(index_type, multispace0)),
+                delimited(
+                    tag("("),
+                    delimited(multispace0, index_col_list, multispace0),
+                    tag(")"),
+                ),
+                opt(preceded(multispace1, index_type)),
+            )),
+            |(before, _, after)| before.is_none() || after.is_none(),
+        ),
+        |(before, columns, after)| (columns, before.or(after)),
+    )(i)
+}
+
 fn primary_key(i: &[u8]) -> IResult<&[u8], TableKey> {
-    let (remaining_input, (_, _, columns, _)) = tuple((
-        tag_no_case("primary key"),
+    let (remaining_input, (_, _, _, _, (columns, index_type), auto_increment)) = tuple((
+        tag_no_case("primary"),
+        multispace1,
+        tag_no_case("key"),
         multispace0,
-        delimited(
-            tag("("),
-            delimited(multispace0, index_col_list, multispace0),
-            tag(")"),
-        ),
-        opt(map(
-            preceded(multispace1, tag_no_case("auto_increment")),
-            |_| (),
-        )),
+        index_columns,
+        opt(preceded(multispace1, tag_no_case("auto_increment"))),
     ))(i)?;
 
-    Ok((remaining_input, TableKey::PrimaryKey(columns)))
+    if let Some(auto_increment) = auto_increment {
+        warning::warn(auto_increment, || {
+            "AUTO_INCREMENT on a PRIMARY KEY definition is not represented".to_string()
+        });
+    }
+
+    Ok((
+        remaining_input,
+        TableKey::PrimaryKey {
+            columns,
+            index_type,
+        },
+    ))
 }
 
 fn unique(i: &[u8]) -> IResult<&[u8], TableKey> {
     // TODO: add branching to correctly parse whitespace after `unique`
-    let (remaining_input, (_, _, _, name, _, columns)) = tuple((
+    let (remaining_input, (_, _, _, name, _, (columns, index_type), comment)) = tuple((
         tag_no_case("unique"),
         opt(preceded(
             multispace1,
@@ -177,89 +850,204 @@ fn unique(i: &[u8]) -> IResult<&[u8], TableKey> {
         multispace0,
         opt(sql_identifier),
         multispace0,
-        delimited(
-            tag("("),
-            delimited(multispace0, index_col_list, multispace0),
-            tag(")"),
-        ),
+        index_columns,
+        opt(parse_comment),
     ))(i)?;
 
-    match name {
-        Some(name) => {
-            let n = String::from_utf8(name.to_vec()).unwrap();
-            Ok((remaining_input, TableKey::UniqueKey(Some(n), columns)))
-        }
-        None => Ok((remaining_input, TableKey::UniqueKey(None, columns))),
-    }
+    let name = name.map(|n| String::from_utf8(n.to_vec()).unwrap());
+    Ok((
+        remaining_input,
+        TableKey::UniqueKey {
+            name,
+            columns,
+            comment,
+            index_type,
+        },
+    ))
 }
 
 fn key_or_index(i: &[u8]) -> IResult<&[u8], TableKey> {
-    let (remaining_input, (_, _, name, _, columns)) = tuple((
+    let (remaining_input, (_, _, name, _, (columns, index_type), comment)) = tuple((
         alt((tag_no_case("key"), tag_no_case("index"))),
         multispace0,
         sql_identifier,
         multispace0,
-        delimited(
-            tag("("),
-            delimited(multispace0, index_col_list, multispace0),
-            tag(")"),
-        ),
+        index_columns,
+        opt(parse_comment),
     ))(i)?;
 
     let n = String::from_utf8(name.to_vec()).unwrap();
-    Ok((remaining_input, TableKey::Key(n, columns)))
+    Ok((
+        remaining_input,
+        TableKey::Key {
+            name: n,
+            columns,
+            comment,
+            index_type,
+        },
+    ))
 }
 
 // Parse rule for a comma-separated list.
 pub fn key_specification_list(i: &[u8]) -> IResult<&[u8], Vec<TableKey>> {
-    many1(terminated(key_specification, opt(ws_sep_comma)))(i)
+    separated_list1(ws_sep_comma, key_specification)(i)
 }
 
-fn field_specification(i: &[u8]) -> IResult<&[u8], ColumnSpecification> {
-    let (remaining_input, (column, field_type, constraints, comment, _)) = tuple((
+// Parse rule for a single column definition.
+pub fn column_specification(i: &[u8]) -> IResult<&[u8], ColumnSpecification> {
+    let (remaining_input, (column, field_type, constraints, comment)) = tuple((
         column_identifier_no_alias,
-        opt(delimited(multispace1, type_identifier, multispace0)),
+        opt(delimited(
+            multispace1,
+            alt((serial_type, map(type_identifier, |t| (t, vec![])))),
+            multispace0,
+        )),
         many0(column_constraint),
         opt(parse_comment),
-        opt(ws_sep_comma),
     ))(i)?;
 
-    let sql_type = match field_type {
-        None => SqlType::Text,
-        Some(ref t) => t.clone(),
-    };
+    let (sql_type, implied_constraints) = field_type.unwrap_or((SqlType::Text, vec![]));
     Ok((
         remaining_input,
         ColumnSpecification {
             column,
             sql_type,
-            constraints: constraints.into_iter().filter_map(|m| m).collect(),
+            constraints: implied_constraints
+                .into_iter()
+                .chain(constraints.into_iter().flatten())
+                .collect(),
             comment,
         },
     ))
 }
 
-// Parse rule for a comma-separated list.
-pub fn field_specification_list(i: &[u8]) -> IResult<&[u8], Vec<ColumnSpecification>> {
-    many1(field_specification)(i)
+// Parse rule for the SERIAL pseudo-types, which stand for an integer type with constraints. In
+// MySQL, SERIAL is BIGINT UNSIGNED NOT NULL AUTO_INCREMENT UNIQUE; in PostgreSQL, SERIAL and
+// BIGSERIAL are auto-incrementing INT and BIGINT. SQLite and standard SQL have no such types.
+fn serial_type(i: &[u8]) -> IResult<&[u8], (SqlType, Vec<ColumnConstraint>)> {
+    let auto_increment = || vec![ColumnConstraint::NotNull, ColumnConstraint::AutoIncrement];
+
+    match dialect::current() {
+        Some(Dialect::PostgreSQL) => alt((
+            map(tag_no_case("bigserial"), |_| {
+                (SqlType::Bigint(None), auto_increment())
+            }),
+            map(tag_no_case("serial"), |_| {
+                (SqlType::Int(None), auto_increment())
+            }),
+        ))(i),
+        Some(Dialect::Ansi) | Some(Dialect::SQLite) => Err(nom::Err::Error(
+            ParseError::from_error_kind(i, ErrorKind::Tag),
+        )),
+        None | Some(Dialect::MySQL) => map(tag_no_case("serial"), |_| {
+            let mut constraints = auto_increment();
+            constraints.push(ColumnConstraint::Unique);
+            (SqlType::UnsignedBigint(Some(20)), constraints)
+        })(i),
+    }
 }
 
-// Parse rule for a column definition constraint.
-pub fn column_constraint(i: &[u8]) -> IResult<&[u8], Option<ColumnConstraint>> {
-    let not_null = map(
-        delimited(multispace0, tag_no_case("not null"), multispace0),
-        |_| Some(ColumnConstraint::NotNull),
-    );
-    let null = map(
-        delimited(multispace0, tag_no_case("null"), multispace0),
-        |_| None,
-    );
-    let auto_increment = map(
-        delimited(multispace0, tag_no_case("auto_increment"), multispace0),
-        |_| Some(ColumnConstraint::AutoIncrement),
-    );
-    let primary_key = map(
-        delimited(multispace0, tag_no_case("primary key"), multispace0),
+/// Like `column_specification`, but also accepts constraints such as `NOT NULL` or `DEFAULT`
+/// written before the column type, as found in some malformed dumps. Constraints are kept in the
+/// order they were written. A column without a type is taken to be TEXT, as in
+/// `column_specification`, but with a warning, since the type may have been lost rather than
+/// omitted. `CREATE TABLE` always uses the strict parser; this is only for standalone definitions.
+pub fn column_specification_lenient(i: &[u8]) -> IResult<&[u8], ColumnSpecification> {
+    let (remaining_input, (column, leading, field_type, trailing, comment)) = tuple((
+        column_identifier_no_alias,
+        many0(column_constraint),
+        opt(delimited(multispace0, type_identifier, multispace0)),
+        many0(column_constraint),
+        opt(parse_comment),
+    ))(i)?;
+
+    if field_type.is_none() {
+        warning::warn(i, || {
+            format!(
+                "column `{}` has no type and is taken to be TEXT",
+                column.name
+            )
+        });
+    }
+    Ok((
+        remaining_input,
+        ColumnSpecification {
+            column,
+            sql_type: field_type.unwrap_or(SqlType::Text),
+            constraints: leading.into_iter().chain(trailing).flatten().collect(),
+            comment,
+        },
+    ))
+}
+
+// Parse rule for a comma-separated list. A comma followed by something other than a column
+// definition (e.g., a key specification) is left unconsumed.
+pub fn field_specification_list(i: &[u8]) -> IResult<&[u8], Vec<ColumnSpecification>> {
+    separated_list1(ws_sep_comma, column_specification)(i)
+}
+
+// Parse rule for a generated column definition: `[GENERATED ALWAYS] AS (expr) [VIRTUAL | STORED]`.
+fn generated(i: &[u8]) -> IResult<&[u8], Option<ColumnConstraint>> {
+    let (remaining_input, (_, _, _, _, expr, stored, _)) = tuple((
+        multispace0,
+        opt(tuple((
+            tag_no_case("generated"),
+            multispace1,
+            tag_no_case("always"),
+            multispace1,
+        ))),
+        tag_no_case("as"),
+        multispace0,
+        map_res(raw_parenthesized_expr, str::from_utf8),
+        opt(preceded(
+            multispace1,
+            alt((
+                map(tag_no_case("virtual"), |_| false),
+                map(tag_no_case("stored"), |_| true),
+            )),
+        )),
+        multispace0,
+    ))(i)?;
+
+    let expr = String::from(expr.trim());
+    Ok((
+        remaining_input,
+        Some(ColumnConstraint::Generated(expr, stored.unwrap_or(false))),
+    ))
+}
+
+// Parse rule for a column definition constraint.
+pub fn column_constraint(i: &[u8]) -> IResult<&[u8], Option<ColumnConstraint>> {
+    let not_null = map(
+        delimited(
+            multispace0,
+            tuple((tag_no_case("not"), multispace1, tag_no_case("null"))),
+            multispace0,
+        ),
+        |_| Some(ColumnConstraint::NotNull),
+    );
+    let null = map(
+        delimited(multispace0, tag_no_case("null"), multispace0),
+        |_| None,
+    );
+    let auto_increment = delimited(
+        multispace0,
+        alt((
+            map(tag_no_case("auto_increment"), |_| {
+                Some(ColumnConstraint::AutoIncrement)
+            }),
+            map(tag_no_case("autoincrement"), |_| {
+                Some(ColumnConstraint::Autoincrement)
+            }),
+        )),
+        multispace0,
+    );
+    let primary_key = map(
+        delimited(
+            multispace0,
+            tuple((tag_no_case("primary"), multispace1, tag_no_case("key"))),
+            multispace0,
+        ),
         |_| Some(ColumnConstraint::PrimaryKey),
     );
     let unique = map(
@@ -268,7 +1056,7 @@ pub fn column_constraint(i: &[u8]) -> IResult<&[u8], Option<ColumnConstraint>> {
     );
     let character_set = map(
         preceded(
-            delimited(multispace0, tag_no_case("character set"), multispace1),
+            delimited(multispace0, charset_keyword, multispace1),
             sql_identifier,
         ),
         |cs| {
@@ -292,79 +1080,201 @@ pub fn column_constraint(i: &[u8]) -> IResult<&[u8], Option<ColumnConstraint>> {
         null,
         auto_increment,
         default,
+        generated,
         primary_key,
         unique,
         character_set,
         collate,
+        references,
+        srid,
     ))(i)
 }
 
-fn fixed_point(i: &[u8]) -> IResult<&[u8], Literal> {
-    let (remaining_input, (i, _, f)) = tuple((digit1, tag("."), digit1))(i)?;
+// Parse rule for the `SRID n` attribute of a spatial column.
+fn srid(i: &[u8]) -> IResult<&[u8], Option<ColumnConstraint>> {
+    map(
+        delimited(
+            delimited(multispace0, tag_no_case("srid"), multispace1),
+            map_res(digit1, |d| str::from_utf8(d).unwrap().parse::<u32>()),
+            multispace0,
+        ),
+        |srid| Some(ColumnConstraint::Srid(srid)),
+    )(i)
+}
+
+// Parse rule for an inline foreign key, `REFERENCES ...` following a column's type.
+fn references(i: &[u8]) -> IResult<&[u8], Option<ColumnConstraint>> {
+    map(
+        delimited(multispace0, foreign_key_reference, multispace0),
+        |reference| Some(ColumnConstraint::References(reference)),
+    )(i)
+}
+
+// Parse rule for the action of an ON DELETE or ON UPDATE clause.
+fn referential_action(i: &[u8]) -> IResult<&[u8], ReferentialAction> {
+    alt((
+        map(tag_no_case("cascade"), |_| ReferentialAction::Cascade),
+        map(
+            tuple((tag_no_case("set"), multispace1, tag_no_case("null"))),
+            |_| ReferentialAction::SetNull,
+        ),
+        map(
+            tuple((tag_no_case("set"), multispace1, tag_no_case("default"))),
+            |_| ReferentialAction::SetDefault,
+        ),
+        map(tag_no_case("restrict"), |_| ReferentialAction::Restrict),
+        map(
+            tuple((tag_no_case("no"), multispace1, tag_no_case("action"))),
+            |_| ReferentialAction::NoAction,
+        ),
+    ))(i)
+}
+
+// Parse rule for the target of a foreign key:
+// `REFERENCES table [(column, ...)] [ON DELETE action] [ON UPDATE action]`.
+fn foreign_key_reference(i: &[u8]) -> IResult<&[u8], ForeignKeyReference> {
+    let on = |event| {
+        tuple((
+            multispace1,
+            tag_no_case("on"),
+            multispace1,
+            tag_no_case(event),
+            multispace1,
+            referential_action,
+        ))
+    };
+    let (remaining_input, (_, _, table, columns, actions)) = tuple((
+        tag_no_case("references"),
+        multispace1,
+        qualified_table_name,
+        opt(preceded(
+            multispace0,
+            delimited(
+                tag("("),
+                delimited(multispace0, key_col_list, multispace0),
+                tag(")"),
+            ),
+        )),
+        many0(alt((
+            map(on("delete"), |t| (Some(t.5), None)),
+            map(on("update"), |t| (None, Some(t.5))),
+        ))),
+    ))(i)?;
 
+    // each of ON DELETE and ON UPDATE may be given once, in either order
+    let (mut on_delete, mut on_update) = (None, None);
+    for (delete, update) in actions {
+        if (delete.is_some() && on_delete.is_some()) || (update.is_some() && on_update.is_some()) {
+            return Err(nom::Err::Error(ParseError::from_error_kind(
+                i,
+                ErrorKind::Verify,
+            )));
+        }
+        on_delete = on_delete.or(delete);
+        on_update = on_update.or(update);
+    }
     Ok((
         remaining_input,
-        Literal::FixedPoint(Real {
-            integral: i32::from_str(str::from_utf8(i).unwrap()).unwrap(),
-            fractional: i32::from_str(str::from_utf8(f).unwrap()).unwrap(),
-        }),
+        ForeignKeyReference {
+            table,
+            columns: columns.unwrap_or_default(),
+            on_delete,
+            on_update,
+        },
     ))
 }
 
+fn default_literal(i: &[u8]) -> IResult<&[u8], Literal> {
+    alt((
+        string_literal,
+        blob_literal,
+        bit_string_literal,
+        numeric_literal,
+        map(tag_no_case("null"), |_| Literal::Null),
+        map(tag_no_case("current_timestamp"), |_| {
+            Literal::CurrentTimestamp
+        }),
+    ))(i)
+}
+
+// MySQL 8 and SQLite also accept an arbitrary expression in parentheses, e.g.
+// `DEFAULT (JSON_ARRAY())`; we keep the expression text as written.
+fn default_expression(i: &[u8]) -> IResult<&[u8], String> {
+    map(map_res(raw_parenthesized_expr, str::from_utf8), |e| {
+        e.trim().to_owned()
+    })(i)
+}
+
 fn default(i: &[u8]) -> IResult<&[u8], Option<ColumnConstraint>> {
-    let (remaining_input, (_, _, _, def, _)) = tuple((
+    let (remaining_input, (_, _, def, _)) = tuple((
         multispace0,
         tag_no_case("default"),
-        multispace1,
         alt((
-            map(
-                delimited(tag("'"), take_until("'"), tag("'")),
-                |s: &[u8]| Literal::String(String::from_utf8(s.to_vec()).unwrap()),
-            ),
-            fixed_point,
-            map(digit1, |d| {
-                let d_i64 = i64::from_str(str::from_utf8(d).unwrap()).unwrap();
-                Literal::Integer(d_i64)
+            map(preceded(multispace0, default_expression), |e| {
+                ColumnConstraint::DefaultExpression(e)
             }),
-            map(tag("''"), |_| Literal::String(String::from(""))),
-            map(tag_no_case("null"), |_| Literal::Null),
-            map(tag_no_case("current_timestamp"), |_| {
-                Literal::CurrentTimestamp
+            map(preceded(multispace1, default_literal), |l| {
+                ColumnConstraint::DefaultValue(l)
             }),
         )),
         multispace0,
     ))(i)?;
 
-    Ok((remaining_input, Some(ColumnConstraint::DefaultValue(def))))
+    Ok((remaining_input, Some(def)))
 }
 
 // Parse rule for a SQL CREATE TABLE query.
 // TODO(malte): support types, TEMPORARY tables, IF NOT EXISTS, AS stmt
 pub fn creation(i: &[u8]) -> IResult<&[u8], CreateTableStatement> {
-    let (remaining_input, (_, _, _, _, table, _, _, _, fields_list, _, keys_list, _, _, _, _, _)) =
+    let (
+        statement_end,
+        (
+            _,
+            _,
+            _,
+            _,
+            table,
+            _,
+            _,
+            _,
+            fields_list,
+            _,
+            keys_list,
+            _,
+            _,
+            _,
+            (options, versioning, sqlite_options),
+            partition,
+        ),
+    ) = tuple((
+        tag_no_case("create"),
+        multispace1,
+        tag_no_case("table"),
+        multispace1,
+        schema_table_reference,
+        multispace0,
+        tag("("),
+        multispace0,
+        field_specification_list,
+        multispace0,
+        opt(preceded(ws_sep_comma, key_specification_list)),
+        multispace0,
+        tag(")"),
+        multispace0,
         tuple((
-            tag_no_case("create"),
-            multispace1,
-            tag_no_case("table"),
-            multispace1,
-            schema_table_reference,
-            multispace0,
-            tag("("),
-            multispace0,
-            field_specification_list,
-            multispace0,
-            opt(key_specification_list),
-            multispace0,
-            tag(")"),
-            multispace0,
             table_options,
-            statement_terminator,
-        ))(i)?;
+            opt(system_versioning),
+            opt(sqlite_table_options),
+        )),
+        opt(partition_clause),
+    ))(i)?;
+    let (remaining_input, _) = statement_terminator(statement_end)?;
 
     // "table AS alias" isn't legal in CREATE statements
     assert!(table.alias.is_none());
+    let (without_rowid, strict) = sqlite_options.unwrap_or_default();
     // attach table names to columns:
-    let fields = fields_list
+    let fields: Vec<ColumnSpecification> = fields_list
         .into_iter()
         .map(|field| {
             let column = Column {
@@ -377,116 +1287,978 @@ pub fn creation(i: &[u8]) -> IResult<&[u8], CreateTableStatement> {
         .collect();
 
     // and to keys:
-    let keys = keys_list.and_then(|ks| {
-        Some(
-            ks.into_iter()
-                .map(|key| {
-                    let attach_names = |columns: Vec<Column>| {
-                        columns
-                            .into_iter()
-                            .map(|column| Column {
-                                table: Some(table.name.clone()),
-                                ..column
-                            })
-                            .collect()
-                    };
-
-                    match key {
-                        TableKey::PrimaryKey(columns) => {
-                            TableKey::PrimaryKey(attach_names(columns))
-                        }
-                        TableKey::UniqueKey(name, columns) => {
-                            TableKey::UniqueKey(name, attach_names(columns))
-                        }
-                        TableKey::FulltextKey(name, columns) => {
-                            TableKey::FulltextKey(name, attach_names(columns))
-                        }
-                        TableKey::Key(name, columns) => TableKey::Key(name, attach_names(columns)),
-                    }
-                })
-                .collect(),
-        )
+    let mut keys: Option<Vec<TableKey>> = keys_list.map(|ks| {
+        ks.into_iter()
+            .map(|key| {
+                let attach_name = |column: Column| Column {
+                    table: Some(table.name.clone()),
+                    ..column
+                };
+                let attach_names =
+                    |columns: Vec<Column>| columns.into_iter().map(attach_name).collect();
+                let attach_index_names = |columns: Vec<IndexColumn>| {
+                    columns
+                        .into_iter()
+                        .map(|c| IndexColumn {
+                            column: attach_name(c.column),
+                            ..c
+                        })
+                        .collect()
+                };
+
+                match key {
+                    TableKey::PrimaryKey {
+                        columns,
+                        index_type,
+                    } => TableKey::PrimaryKey {
+                        columns: attach_index_names(columns),
+                        index_type,
+                    },
+                    TableKey::UniqueKey {
+                        name,
+                        columns,
+                        comment,
+                        index_type,
+                    } => TableKey::UniqueKey {
+                        name,
+                        columns: attach_index_names(columns),
+                        comment,
+                        index_type,
+                    },
+                    TableKey::FulltextKey {
+                        name,
+                        columns,
+                        comment,
+                    } => TableKey::FulltextKey {
+                        name,
+                        columns: attach_index_names(columns),
+                        comment,
+                    },
+                    TableKey::Key {
+                        name,
+                        columns,
+                        comment,
+                        index_type,
+                    } => TableKey::Key {
+                        name,
+                        columns: attach_index_names(columns),
+                        comment,
+                        index_type,
+                    },
+                    TableKey::ForeignKey {
+                        name,
+                        index_name,
+                        columns,
+                        reference,
+                    } => TableKey::ForeignKey {
+                        name,
+                        index_name,
+                        columns: attach_names(columns),
+                        reference,
+                    },
+                }
+            })
+            .collect()
     });
 
+    // a primary key declared inline on its column is also recorded as a table key; only one column
+    // may be declared that way, and not alongside a table-level primary key
+    let inline_primary_key: Vec<Column> = fields
+        .iter()
+        .filter(|f| f.constraints.contains(&ColumnConstraint::PrimaryKey))
+        .map(|f| f.column.clone())
+        .collect();
+    let has_primary_key = keys
+        .iter()
+        .flatten()
+        .any(|k| matches!(*k, TableKey::PrimaryKey { .. }));
+    if inline_primary_key.len() > 1 || (!inline_primary_key.is_empty() && has_primary_key) {
+        return Err(nom::Err::Error(ParseError::from_error_kind(
+            i,
+            ErrorKind::Verify,
+        )));
+    }
+    if !inline_primary_key.is_empty() {
+        keys.get_or_insert_with(Vec::new).insert(
+            0,
+            TableKey::PrimaryKey {
+                columns: inline_primary_key
+                    .into_iter()
+                    .map(IndexColumn::from)
+                    .collect(),
+                index_type: None,
+            },
+        );
+    }
+    // and so are inline foreign keys
+    for field in &fields {
+        for constraint in &field.constraints {
+            if let ColumnConstraint::References(ref reference) = *constraint {
+                keys.get_or_insert_with(Vec::new)
+                    .push(TableKey::ForeignKey {
+                        name: None,
+                        index_name: None,
+                        columns: vec![field.column.clone()],
+                        reference: reference.clone(),
+                    });
+            }
+        }
+    }
+
     Ok((
         remaining_input,
         CreateTableStatement {
             table,
             fields,
             keys,
+            options,
+            system_versioning: versioning.is_some(),
+            without_rowid,
+            strict,
+            partition,
+            span: span::span(i, statement_end),
+        },
+    ))
+}
+
+// Parse rule for MariaDB's WITH SYSTEM VERSIONING table option.
+fn system_versioning(i: &[u8]) -> IResult<&[u8], ()> {
+    let (remaining_input, _) = tuple((
+        multispace0,
+        tag_no_case("with"),
+        multispace1,
+        tag_no_case("system"),
+        multispace1,
+        tag_no_case("versioning"),
+    ))(i)?;
+
+    Ok((remaining_input, ()))
+}
+
+// Parse rule for SQLite's comma-separated table options, returning whether WITHOUT ROWID and
+// STRICT were given.
+fn sqlite_table_options(i: &[u8]) -> IResult<&[u8], (bool, bool)> {
+    let without_rowid = map(
+        tuple((tag_no_case("without"), multispace1, tag_no_case("rowid"))),
+        |_| (true, false),
+    );
+    let strict = map(tag_no_case("strict"), |_| (false, true));
+
+    let (remaining_input, options) = preceded(
+        multispace0,
+        separated_list1(ws_sep_comma, alt((without_rowid, strict))),
+    )(i)?;
+
+    Ok((
+        remaining_input,
+        options
+            .into_iter()
+            .fold((false, false), |acc, o| (acc.0 || o.0, acc.1 || o.1)),
+    ))
+}
+
+// Parse rule for a trailing PARTITION BY clause, which is kept as raw text.
+fn partition_clause(i: &[u8]) -> IResult<&[u8], String> {
+    let (remaining_input, (_, _, _, _, _, partition)) = tuple((
+        multispace0,
+        tag_no_case("partition"),
+        multispace1,
+        tag_no_case("by"),
+        multispace1,
+        map_res(is_not(";"), str::from_utf8),
+    ))(i)?;
+
+    Ok((remaining_input, partition.trim().to_owned()))
+}
+
+// Parse rule for a SQL CREATE [UNIQUE | FULLTEXT] INDEX query.
+pub fn index_creation(i: &[u8]) -> IResult<&[u8], CreateIndexStatement> {
+    let (
+        remaining_input,
+        (_, _, kind, _, _, name, _, _, _, table, _, columns, nulls_not_distinct, comment, _),
+    ) = tuple((
+        tag_no_case("create"),
+        multispace1,
+        opt(terminated(
+            alt((
+                map(tag_no_case("unique"), |_| (true, false)),
+                map(tag_no_case("fulltext"), |_| (false, true)),
+            )),
+            multispace1,
+        )),
+        tag_no_case("index"),
+        multispace1,
+        sql_identifier,
+        multispace1,
+        tag_no_case("on"),
+        multispace1,
+        qualified_table_name,
+        multispace0,
+        delimited(
+            tag("("),
+            delimited(multispace0, index_col_list, multispace0),
+            tag(")"),
+        ),
+        opt(tuple((
+            multispace1,
+            tag_no_case("nulls"),
+            multispace1,
+            tag_no_case("not"),
+            multispace1,
+            tag_no_case("distinct"),
+        ))),
+        opt(parse_comment),
+        statement_terminator,
+    ))(i)?;
+
+    let (unique, fulltext) = kind.unwrap_or((false, false));
+    let columns = columns
+        .into_iter()
+        .map(|c: IndexColumn| IndexColumn {
+            column: Column {
+                table: Some(table.name.clone()),
+                ..c.column
+            },
+            ..c
+        })
+        .collect();
+
+    Ok((
+        remaining_input,
+        CreateIndexStatement {
+            name: String::from_utf8(name.to_vec()).unwrap(),
+            table,
+            columns,
+            unique,
+            fulltext,
+            nulls_not_distinct: nulls_not_distinct.is_some(),
+            comment,
         },
     ))
 }
 
-// Parse rule for a SQL CREATE VIEW query.
-pub fn view_creation(i: &[u8]) -> IResult<&[u8], CreateViewStatement> {
-    let (remaining_input, (_, _, _, _, name_slice, _, _, _, def, _)) = tuple((
-        tag_no_case("create"),
-        multispace1,
-        tag_no_case("view"),
-        multispace1,
-        sql_identifier,
-        multispace1,
-        tag_no_case("as"),
-        multispace1,
-        alt((
-            map(compound_selection, |s| SelectSpecification::Compound(s)),
-            map(nested_selection, |s| SelectSpecification::Simple(s)),
-        )),
-        statement_terminator,
-    ))(i)?;
+// An option of a CREATE DATABASE statement.
+enum DatabaseOption {
+    CharacterSet(String),
+    Collation(String),
+}
+
+// Parse rule for a `[DEFAULT] CHARACTER SET` or `[DEFAULT] COLLATE` database option.
+fn database_option(i: &[u8]) -> IResult<&[u8], DatabaseOption> {
+    let (remaining_input, (_, option, _, value)) = tuple((
+        opt(terminated(tag_no_case("default"), multispace1)),
+        alt((
+            map(charset_keyword, |_| true),
+            map(tag_no_case("collate"), |_| false),
+        )),
+        alt((ws_sep_equals, multispace1)),
+        sql_identifier,
+    ))(i)?;
+
+    let value = String::from_utf8(value.to_vec()).unwrap();
+    if option {
+        Ok((remaining_input, DatabaseOption::CharacterSet(value)))
+    } else {
+        Ok((remaining_input, DatabaseOption::Collation(value)))
+    }
+}
+
+// Parse rule for a SQL CREATE DATABASE (or CREATE SCHEMA) query; the character set and collation
+// may be given in either order.
+pub fn database_creation(i: &[u8]) -> IResult<&[u8], CreateDatabaseStatement> {
+    let (remaining_input, (_, _, _, _, name, options, _)) = tuple((
+        tag_no_case("create"),
+        multispace1,
+        alt((tag_no_case("database"), tag_no_case("schema"))),
+        multispace1,
+        sql_identifier,
+        many0(preceded(multispace1, database_option)),
+        statement_terminator,
+    ))(i)?;
+
+    let mut statement = CreateDatabaseStatement {
+        name: String::from_utf8(name.to_vec()).unwrap(),
+        ..Default::default()
+    };
+    for option in options {
+        match option {
+            DatabaseOption::CharacterSet(charset) => statement.charset = Some(charset),
+            DatabaseOption::Collation(collation) => statement.collation = Some(collation),
+        }
+    }
+    Ok((remaining_input, statement))
+}
+
+// Parse rule for a view's `WITH [CASCADED | LOCAL] CHECK OPTION` clause.
+fn view_check_option(i: &[u8]) -> IResult<&[u8], ViewCheckOption> {
+    let (remaining_input, (_, _, _, scope, _, _, _)) = tuple((
+        multispace0,
+        tag_no_case("with"),
+        multispace1,
+        opt(terminated(
+            alt((
+                map(tag_no_case("cascaded"), |_| ViewCheckOption::Cascaded),
+                map(tag_no_case("local"), |_| ViewCheckOption::Local),
+            )),
+            multispace1,
+        )),
+        tag_no_case("check"),
+        multispace1,
+        tag_no_case("option"),
+    ))(i)?;
+
+    Ok((remaining_input, scope.unwrap_or(ViewCheckOption::Cascaded)))
+}
+
+// Parse rule for a SQL CREATE VIEW query.
+pub fn view_creation(i: &[u8]) -> IResult<&[u8], CreateViewStatement> {
+    let (remaining_input, (_, _, _, _, name_slice, _, _, _, def, check_option, _)) = tuple((
+        tag_no_case("create"),
+        multispace1,
+        tag_no_case("view"),
+        multispace1,
+        sql_identifier,
+        multispace1,
+        tag_no_case("as"),
+        multispace1,
+        alt((
+            map(compound_selection, |s| SelectSpecification::Compound(s)),
+            map(nested_selection, |s| SelectSpecification::Simple(s)),
+        )),
+        opt(view_check_option),
+        statement_terminator,
+    ))(i)?;
+
+    let name = String::from_utf8(name_slice.to_vec()).unwrap();
+    let fields = vec![]; // TODO(malte): support
+    let definition = Box::new(def);
+
+    Ok((
+        remaining_input,
+        CreateViewStatement {
+            name,
+            fields,
+            definition,
+            check_option,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use column::Column;
+    use create_table_options::RowFormat;
+    use order::OrderType;
+    use table::Table;
+
+    #[test]
+    fn sql_types() {
+        let type0 = "bigint(20)";
+        let type1 = "varchar(255) binary";
+        let type2 = "bigint(20) unsigned";
+        let type3 = "bigint(20) signed";
+
+        let res = type_identifier(type0.as_bytes());
+        assert_eq!(res.unwrap().1, SqlType::Bigint(Some(20)));
+        let res = type_identifier(type1.as_bytes());
+        assert_eq!(res.unwrap().1, SqlType::Varchar(255));
+        let res = type_identifier(type2.as_bytes());
+        assert_eq!(res.unwrap().1, SqlType::UnsignedBigint(Some(20)));
+        let res = type_identifier(type3.as_bytes());
+        assert_eq!(res.unwrap().1, SqlType::Bigint(Some(20)));
+        let res = type_identifier(type2.as_bytes());
+        assert_eq!(res.unwrap().1, SqlType::UnsignedBigint(Some(20)));
+    }
+
+    #[test]
+    fn field_spec() {
+        let qstring = "id bigint(20), name varchar(255)";
+
+        let res = field_specification_list(qstring.as_bytes());
+        assert_eq!(
+            res.unwrap().1,
+            vec![
+                ColumnSpecification::new(Column::from("id"), SqlType::Bigint(Some(20))),
+                ColumnSpecification::new(Column::from("name"), SqlType::Varchar(255)),
+            ]
+        );
+    }
+
+    #[test]
+    fn charset_shorthand() {
+        let qstring = "CREATE TABLE t (a varchar(10) CHARSET utf8mb4, \
+                       b text CHARACTER SET latin1) CHARSET=utf8mb4;";
+
+        let res = creation(qstring.as_bytes());
+        assert_eq!(
+            res.unwrap().1.fields,
+            vec![
+                ColumnSpecification::with_constraints(
+                    Column::from("t.a"),
+                    SqlType::Varchar(10),
+                    vec![ColumnConstraint::CharacterSet(String::from("utf8mb4"))],
+                ),
+                ColumnSpecification::with_constraints(
+                    Column::from("t.b"),
+                    SqlType::Text,
+                    vec![ColumnConstraint::CharacterSet(String::from("latin1"))],
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_charset_without_equals() {
+        let qstring1 = "CREATE TABLE t (a int) ENGINE=InnoDB DEFAULT CHARSET utf8mb4;";
+        let qstring2 = "CREATE TABLE t (a int) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;";
+
+        let res1 = creation(qstring1.as_bytes());
+        let res2 = creation(qstring2.as_bytes());
+        assert_eq!(res1.unwrap(), res2.unwrap());
+    }
+
+    #[test]
+    fn scale_string_lengths() {
+        let qstring = "CREATE TABLE t (a varchar(100), b char(40000), c binary(16), d int(11));";
+
+        let mut res = creation(qstring.as_bytes()).unwrap().1;
+        res.scale_string_lengths(2.0);
+        let types: Vec<_> = res.fields.iter().map(|f| f.sql_type.clone()).collect();
+        assert_eq!(
+            types,
+            vec![
+                SqlType::Varchar(200),
+                SqlType::Char(u16::MAX),
+                SqlType::Binary(32),
+                SqlType::Int(Some(11)),
+            ]
+        );
+    }
+
+    #[test]
+    fn generated_columns() {
+        let qstring = "CREATE TABLE t (a int, b int, \
+                       total int GENERATED ALWAYS AS (a + b), \
+                       label varchar(20) AS (CONCAT(`t`.`a`, 'x', b, a)) STORED NOT NULL);";
+
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.fields[2].constraints,
+            vec![ColumnConstraint::Generated(String::from("a + b"), false)]
+        );
+        assert_eq!(
+            res.fields[3].constraints,
+            vec![
+                ColumnConstraint::Generated(String::from("CONCAT(`t`.`a`, 'x', b, a)"), true),
+                ColumnConstraint::NotNull,
+            ]
+        );
+        assert_eq!(res.fields[2].generation_dependencies(), vec!["a", "b"]);
+        assert_eq!(res.fields[3].generation_dependencies(), vec!["a", "b"]);
+        assert!(res.fields[0].generation_dependencies().is_empty());
+    }
+
+    #[test]
+    fn single_column_and_key() {
+        let qstring = "CREATE TABLE t (id int, PRIMARY KEY (id));";
+
+        let res = creation(qstring.as_bytes());
+        assert_eq!(
+            res.unwrap().1,
+            CreateTableStatement {
+                table: Table::from("t"),
+                fields: vec![ColumnSpecification::new(
+                    Column::from("t.id"),
+                    SqlType::Int(None)
+                )],
+                keys: Some(vec![TableKey::PrimaryKey {
+                    columns: vec![IndexColumn::from("t.id")],
+                    index_type: None
+                }]),
+                ..Default::default()
+            }
+        );
+
+        // columns and keys must be comma-separated
+        let qstring = "CREATE TABLE t (id int name text);";
+        assert!(creation(qstring.as_bytes()).is_err());
+        let qstring = "CREATE TABLE t (id int PRIMARY KEY (id));";
+        assert!(creation(qstring.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn create_system_versioned_table() {
+        let qstring = "CREATE TABLE t (id int) ENGINE=InnoDB WITH SYSTEM VERSIONING;";
+
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert!(res.system_versioning);
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE t (id INT) WITH SYSTEM VERSIONING"
+        );
+
+        let res = creation(b"CREATE TABLE t (id int);").unwrap().1;
+        assert!(!res.system_versioning);
+    }
+
+    #[test]
+    fn create_sqlite_without_rowid() {
+        let qstring = "CREATE TABLE t (id int PRIMARY KEY, v text) WITHOUT ROWID;";
+
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert!(res.without_rowid);
+        assert!(!res.strict);
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE t (id INT PRIMARY KEY, v TEXT) WITHOUT ROWID"
+        );
+    }
+
+    #[test]
+    fn equality_ignoring_keys() {
+        let with_keys = "CREATE TABLE t (id int, name varchar(10), PRIMARY KEY (id), KEY n (name))";
+        let without_keys = "CREATE TABLE t (id int, name varchar(10))";
+        let a = creation(with_keys.as_bytes()).unwrap().1;
+        let b = creation(without_keys.as_bytes()).unwrap().1;
+        assert_ne!(a, b);
+        assert!(a.columns_equal(&b));
+        assert!(!a.keys_equal(&b));
+
+        let reordered = "CREATE TABLE t (name varchar(10), id int, KEY n (name), PRIMARY KEY (id))";
+        let c = creation(reordered.as_bytes()).unwrap().1;
+        assert!(!a.columns_equal(&c));
+        assert!(a.keys_equal(&c));
+
+        let empty = CreateTableStatement {
+            keys: Some(vec![]),
+            ..b.clone()
+        };
+        assert!(b.keys_equal(&empty));
+
+        // keys are compared as multisets
+        let (primary, name) = match a.keys {
+            Some(ref keys) => (keys[0].clone(), keys[1].clone()),
+            None => unreachable!(),
+        };
+        let d = CreateTableStatement {
+            keys: Some(vec![primary.clone(), name.clone(), name.clone()]),
+            ..b.clone()
+        };
+        let e = CreateTableStatement {
+            keys: Some(vec![primary.clone(), primary, name]),
+            ..b.clone()
+        };
+        assert!(!d.keys_equal(&e));
+
+        // columns are compared regardless of the table they are qualified with
+        let renamed = "CREATE TABLE u (id int, name varchar(10))";
+        let f = creation(renamed.as_bytes()).unwrap().1;
+        assert_ne!(b.fields, f.fields);
+        assert!(b.columns_equal(&f));
+    }
+
+    #[test]
+    fn spatial_column_srid() {
+        let qstring = "CREATE TABLE places (geom GEOMETRY NOT NULL SRID 4326, p POINT SRID 0)";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.fields[0].sql_type, SqlType::Geometry);
+        assert_eq!(
+            res.fields[0].constraints,
+            vec![ColumnConstraint::NotNull, ColumnConstraint::Srid(4326)]
+        );
+        assert_eq!(res.fields[1].constraints, vec![ColumnConstraint::Srid(0)]);
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE places (geom GEOMETRY NOT NULL SRID 4326, p POINT SRID 0)"
+        );
+    }
+
+    #[test]
+    fn create_auto_increment_start() {
+        let qstring = "CREATE TABLE t (id int AUTO_INCREMENT PRIMARY KEY) \
+                       ENGINE=InnoDB AUTO_INCREMENT=1000 DEFAULT CHARSET=utf8mb4";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.options, vec![TableOption::AutoIncrement(1000)]);
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE t (id INT AUTO_INCREMENT PRIMARY KEY) AUTO_INCREMENT=1000"
+        );
+
+        let res = creation(b"CREATE TABLE t (id int) ENGINE=InnoDB")
+            .unwrap()
+            .1;
+        assert!(res.options.is_empty());
+    }
+
+    #[test]
+    fn create_row_format_and_key_block_size() {
+        let qstring = "CREATE TABLE t (id int) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 \
+                       ROW_FORMAT=DYNAMIC KEY_BLOCK_SIZE=8;";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.options,
+            vec![
+                TableOption::RowFormat(RowFormat::Dynamic),
+                TableOption::KeyBlockSize(8),
+            ]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE t (id INT) ROW_FORMAT=DYNAMIC KEY_BLOCK_SIZE=8"
+        );
+    }
+
+    #[test]
+    fn create_sqlite_strict() {
+        let res = creation(b"CREATE TABLE t (id integer, v text) strict")
+            .unwrap()
+            .1;
+        assert!(res.strict);
+        assert!(!res.without_rowid);
+        assert_eq!(format!("{}", res), "CREATE TABLE t (id INT, v TEXT) STRICT");
+
+        let res = creation(b"CREATE TABLE t (id int);").unwrap().1;
+        assert!(!res.strict);
+        assert!(!res.without_rowid);
+    }
+
+    #[test]
+    fn create_sqlite_strict_without_rowid() {
+        let expected = "CREATE TABLE t (id INT PRIMARY KEY) WITHOUT ROWID, STRICT";
+
+        let res = creation(b"CREATE TABLE t (id int PRIMARY KEY) STRICT, WITHOUT ROWID;")
+            .unwrap()
+            .1;
+        assert!(res.strict && res.without_rowid);
+        assert_eq!(format!("{}", res), expected);
+
+        let res = creation(b"CREATE TABLE t (id int PRIMARY KEY) WITHOUT ROWID , STRICT")
+            .unwrap()
+            .1;
+        assert!(res.strict && res.without_rowid);
+        assert_eq!(format!("{}", res), expected);
+    }
+
+    #[test]
+    fn sqlite_inline_constraints() {
+        let qstring = "CREATE TABLE posts (
+                       id INTEGER PRIMARY KEY AUTOINCREMENT,
+                       slug TEXT NOT NULL UNIQUE,
+                       author_id INTEGER REFERENCES users(id),
+                       parent INTEGER REFERENCES posts)";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.fields[0],
+            ColumnSpecification::with_constraints(
+                Column::from("posts.id"),
+                SqlType::Int(None),
+                vec![
+                    ColumnConstraint::PrimaryKey,
+                    ColumnConstraint::Autoincrement
+                ],
+            )
+        );
+        assert_eq!(
+            res.fields[1].constraints,
+            vec![ColumnConstraint::NotNull, ColumnConstraint::Unique]
+        );
+        let users_id = ForeignKeyReference {
+            table: Table::from("users"),
+            columns: vec![Column::from("id")],
+            on_delete: None,
+            on_update: None,
+        };
+        let posts = ForeignKeyReference {
+            table: Table::from("posts"),
+            columns: vec![],
+            on_delete: None,
+            on_update: None,
+        };
+        assert_eq!(
+            res.fields[2].constraints,
+            vec![ColumnConstraint::References(users_id.clone())]
+        );
+        assert_eq!(
+            res.fields[3].constraints,
+            vec![ColumnConstraint::References(posts.clone())]
+        );
+        assert_eq!(
+            res.keys,
+            Some(vec![
+                TableKey::PrimaryKey {
+                    columns: vec![IndexColumn::from("posts.id")],
+                    index_type: None
+                },
+                TableKey::ForeignKey {
+                    name: None,
+                    index_name: None,
+                    columns: vec![Column::from("posts.author_id")],
+                    reference: users_id
+                },
+                TableKey::ForeignKey {
+                    name: None,
+                    index_name: None,
+                    columns: vec![Column::from("posts.parent")],
+                    reference: posts
+                },
+            ])
+        );
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE posts (id INT PRIMARY KEY AUTOINCREMENT, \
+             slug TEXT NOT NULL UNIQUE, author_id INT REFERENCES users (id), \
+             parent INT REFERENCES posts)"
+        );
+
+        // only one column may be declared the primary key inline
+        let qstring = "CREATE TABLE t (a int PRIMARY KEY, b int PRIMARY KEY)";
+        assert!(creation(qstring.as_bytes()).is_err());
+
+        // nor alongside a table-level primary key
+        let qstring = "CREATE TABLE t (id int PRIMARY KEY, v int, PRIMARY KEY (v))";
+        assert!(creation(qstring.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn inline_references_with_action() {
+        let qstring = "CREATE TABLE orders (id int, \
+                       user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE)";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        let reference = ForeignKeyReference {
+            table: Table::from("users"),
+            columns: vec![Column::from("id")],
+            on_delete: Some(ReferentialAction::Cascade),
+            on_update: None,
+        };
+        assert_eq!(
+            res.fields[1].constraints,
+            vec![
+                ColumnConstraint::NotNull,
+                ColumnConstraint::References(reference.clone())
+            ]
+        );
+        assert_eq!(
+            res.keys,
+            Some(vec![TableKey::ForeignKey {
+                name: None,
+                index_name: None,
+                columns: vec![Column::from("orders.user_id")],
+                reference
+            }])
+        );
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE orders (id INT, \
+             user_id INT NOT NULL REFERENCES users (id) ON DELETE CASCADE)"
+        );
+    }
+
+    #[test]
+    fn table_foreign_keys() {
+        let qstring = "CREATE TABLE orders (id int, user_id int, shop_id int, \
+                       FOREIGN KEY (user_id) REFERENCES users (id), \
+                       CONSTRAINT fk_shop FOREIGN KEY shop_idx (shop_id) REFERENCES shops(id) \
+                       ON UPDATE SET NULL ON DELETE NO ACTION)";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.keys,
+            Some(vec![
+                TableKey::ForeignKey {
+                    name: None,
+                    index_name: None,
+                    columns: vec![Column::from("orders.user_id")],
+                    reference: ForeignKeyReference {
+                        table: Table::from("users"),
+                        columns: vec![Column::from("id")],
+                        on_delete: None,
+                        on_update: None,
+                    }
+                },
+                TableKey::ForeignKey {
+                    name: Some(String::from("fk_shop")),
+                    index_name: Some(String::from("shop_idx")),
+                    columns: vec![Column::from("orders.shop_id")],
+                    reference: ForeignKeyReference {
+                        table: Table::from("shops"),
+                        columns: vec![Column::from("id")],
+                        on_delete: Some(ReferentialAction::NoAction),
+                        on_update: Some(ReferentialAction::SetNull),
+                    }
+                },
+            ])
+        );
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE orders (id INT, user_id INT, shop_id INT, \
+             FOREIGN KEY (user_id) REFERENCES users (id), \
+             CONSTRAINT fk_shop FOREIGN KEY shop_idx (shop_id) REFERENCES shops (id) \
+             ON DELETE NO ACTION ON UPDATE SET NULL)"
+        );
 
-    let name = String::from_utf8(name_slice.to_vec()).unwrap();
-    let fields = vec![]; // TODO(malte): support
-    let definition = Box::new(def);
+        // each referential action may only be given once
+        let qstring = "CREATE TABLE orders (user_id int, \
+                       FOREIGN KEY (user_id) REFERENCES users (id) \
+                       ON DELETE CASCADE ON DELETE SET NULL)";
+        assert!(creation(qstring.as_bytes()).is_err());
+    }
 
-    Ok((
-        remaining_input,
-        CreateViewStatement {
-            name,
-            fields,
-            definition,
-        },
-    ))
-}
+    #[test]
+    fn index_column_collation() {
+        let res = index_col_name(b"name(10) COLLATE utf8mb4_bin DESC")
+            .unwrap()
+            .1;
+        assert_eq!(
+            res,
+            IndexColumn {
+                column: Column::from("name"),
+                length: Some(10),
+                collation: Some(String::from("utf8mb4_bin")),
+                order: Some(OrderType::OrderDescending),
+            }
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use column::Column;
-    use table::Table;
+        let qstring = "CREATE TABLE t (name varchar(10), KEY idx (name COLLATE utf8mb4_bin))";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.keys,
+            Some(vec![TableKey::Key {
+                name: String::from("idx"),
+                columns: vec![IndexColumn {
+                    collation: Some(String::from("utf8mb4_bin")),
+                    ..IndexColumn::from("t.name")
+                }],
+                comment: None,
+                index_type: None
+            }])
+        );
+    }
 
     #[test]
-    fn sql_types() {
-        let type0 = "bigint(20)";
-        let type1 = "varchar(255) binary";
-        let type2 = "bigint(20) unsigned";
-        let type3 = "bigint(20) signed";
+    fn format_index_column_options() {
+        for qstring in &[
+            "CREATE TABLE t (name VARCHAR(20), a INT, \
+             KEY idx (name(10) COLLATE utf8mb4_bin DESC, a ASC))",
+            "CREATE TABLE t (name VARCHAR(20), PRIMARY KEY (name(5)))",
+        ] {
+            let res = creation(qstring.as_bytes()).unwrap().1;
+            assert_eq!(format!("{}", res), *qstring);
+        }
 
-        let res = type_identifier(type0.as_bytes());
-        assert_eq!(res.unwrap().1, SqlType::Bigint(20));
-        let res = type_identifier(type1.as_bytes());
-        assert_eq!(res.unwrap().1, SqlType::Varchar(255));
-        let res = type_identifier(type2.as_bytes());
-        assert_eq!(res.unwrap().1, SqlType::UnsignedBigint(20));
-        let res = type_identifier(type3.as_bytes());
-        assert_eq!(res.unwrap().1, SqlType::Bigint(20));
-        let res = type_identifier(type2.as_bytes());
-        assert_eq!(res.unwrap().1, SqlType::UnsignedBigint(20));
+        let qstring = "CREATE UNIQUE INDEX idx ON t (name(10) DESC, a)";
+        let res = index_creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(format!("{}", res), qstring);
     }
 
     #[test]
-    fn field_spec() {
-        // N.B. trailing comma here because field_specification_list! doesn't handle the eof case
-        // because it is never validly the end of a query
-        let qstring = "id bigint(20), name varchar(255),";
+    fn index_types() {
+        let qstring = "CREATE TABLE t (a int, b int, PRIMARY KEY USING HASH (a), \
+                       KEY b_idx USING BTREE (b), UNIQUE KEY ab (a, b) USING HASH COMMENT 'pair', \
+                       INDEX plain (b))";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.keys,
+            Some(vec![
+                TableKey::PrimaryKey {
+                    columns: vec![IndexColumn::from("t.a")],
+                    index_type: Some(IndexType::Hash)
+                },
+                TableKey::Key {
+                    name: String::from("b_idx"),
+                    columns: vec![IndexColumn::from("t.b")],
+                    comment: None,
+                    index_type: Some(IndexType::BTree)
+                },
+                TableKey::UniqueKey {
+                    name: Some(String::from("ab")),
+                    columns: vec![IndexColumn::from("t.a"), IndexColumn::from("t.b")],
+                    comment: Some(String::from("pair")),
+                    index_type: Some(IndexType::Hash)
+                },
+                TableKey::Key {
+                    name: String::from("plain"),
+                    columns: vec![IndexColumn::from("t.b")],
+                    comment: None,
+                    index_type: None
+                },
+            ])
+        );
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE t (a INT, b INT, PRIMARY KEY (a) USING HASH, \
+             KEY b_idx (b) USING BTREE, UNIQUE KEY ab (a, b) USING HASH COMMENT 'pair', \
+             KEY plain (b))"
+        );
+        assert_eq!(
+            creation(format!("{}", res).as_bytes()).unwrap().1.keys,
+            res.keys
+        );
+        // the index method may only be given once
+        let qstring = "CREATE TABLE t (a int, KEY a_idx USING HASH (a) USING BTREE)";
+        assert!(creation(qstring.as_bytes()).is_err());
+    }
 
-        let res = field_specification_list(qstring.as_bytes());
+    #[test]
+    fn index_comments() {
+        let qstring = "CREATE TABLE t (a int, b int, KEY a_idx (a) COMMENT 'lookup by a', \
+                       UNIQUE KEY b_idx (b), FULLTEXT KEY ft (a, b) COMMENT 'search')";
+        let res = creation(qstring.as_bytes()).unwrap().1;
         assert_eq!(
-            res.unwrap().1,
+            res.keys,
+            Some(vec![
+                TableKey::Key {
+                    name: String::from("a_idx"),
+                    columns: vec![IndexColumn::from("t.a")],
+                    comment: Some(String::from("lookup by a")),
+                    index_type: None
+                },
+                TableKey::UniqueKey {
+                    name: Some(String::from("b_idx")),
+                    columns: vec![IndexColumn::from("t.b")],
+                    comment: None,
+                    index_type: None
+                },
+                TableKey::FulltextKey {
+                    name: Some(String::from("ft")),
+                    columns: vec![IndexColumn::from("t.a"), IndexColumn::from("t.b")],
+                    comment: Some(String::from("search"))
+                },
+            ])
+        );
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE t (a INT, b INT, KEY a_idx (a) COMMENT 'lookup by a', \
+             UNIQUE KEY b_idx (b), FULLTEXT KEY ft (a, b) COMMENT 'search')"
+        );
+
+        let (_, indexes) = res.with_separate_indexes();
+        assert_eq!(indexes[0].comment, Some(String::from("lookup by a")));
+        assert_eq!(indexes[1].comment, None);
+
+        let qstring =
+            "CREATE TABLE t (a INT COMMENT 'it''s a', KEY a_idx (a) COMMENT 'a''s index')";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.fields[0].comment, Some(String::from("it's a")));
+        assert_eq!(format!("{}", res), qstring);
+    }
+
+    #[test]
+    fn default_literals() {
+        let qstring = "CREATE TABLE t (a text DEFAULT 'it''s', b float DEFAULT -0.5, \
+                       c int DEFAULT +3, d blob DEFAULT X'0a')";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        let defaults: Vec<_> = res
+            .fields
+            .iter()
+            .map(|f| f.constraints[0].clone())
+            .collect();
+        assert_eq!(
+            defaults,
             vec![
-                ColumnSpecification::new(Column::from("id"), SqlType::Bigint(20)),
-                ColumnSpecification::new(Column::from("name"), SqlType::Varchar(255)),
+                ColumnConstraint::DefaultValue(Literal::String(String::from("it's"))),
+                ColumnConstraint::DefaultValue(Literal::Float(-0.5)),
+                ColumnConstraint::DefaultValue(Literal::Integer(3)),
+                ColumnConstraint::DefaultValue(Literal::Blob(vec![0x0a])),
             ]
         );
     }
@@ -501,7 +2273,7 @@ mod tests {
             CreateTableStatement {
                 table: Table::from("users"),
                 fields: vec![
-                    ColumnSpecification::new(Column::from("users.id"), SqlType::Bigint(20)),
+                    ColumnSpecification::new(Column::from("users.id"), SqlType::Bigint(Some(20))),
                     ColumnSpecification::new(Column::from("users.name"), SqlType::Varchar(255)),
                     ColumnSpecification::new(Column::from("users.email"), SqlType::Varchar(255)),
                 ],
@@ -520,7 +2292,7 @@ mod tests {
                 table: Table::from("t"),
                 fields: vec![ColumnSpecification::new(
                     Column::from("t.x"),
-                    SqlType::Int(32)
+                    SqlType::Int(None)
                 ),],
                 ..Default::default()
             }
@@ -537,13 +2309,139 @@ mod tests {
                 table: Table::from(("db1","t")),
                 fields: vec![ColumnSpecification::new(
                     Column::from("t.x"),
-                    SqlType::Int(32)
+                    SqlType::Int(None)
                 ),],
                 ..Default::default()
             }
         );
     }
 
+    #[test]
+    fn create_with_irregular_whitespace() {
+        let qstring = "CREATE TABLE  users (  \n\t id  bigint( 20 )  NOT   NULL ,name varchar(255)\
+                       \n  ,  email varchar( 255 ) ,\n\n PRIMARY   KEY  (  id  ) ,  \
+                       UNIQUE KEY  email_k ( email ( 10 ) )\n\t )  ;";
+
+        let res = creation(qstring.as_bytes());
+        assert_eq!(
+            res.unwrap().1,
+            CreateTableStatement {
+                table: Table::from("users"),
+                fields: vec![
+                    ColumnSpecification::with_constraints(
+                        Column::from("users.id"),
+                        SqlType::Bigint(Some(20)),
+                        vec![ColumnConstraint::NotNull],
+                    ),
+                    ColumnSpecification::new(Column::from("users.name"), SqlType::Varchar(255)),
+                    ColumnSpecification::new(Column::from("users.email"), SqlType::Varchar(255)),
+                ],
+                keys: Some(vec![
+                    TableKey::PrimaryKey {
+                        columns: vec![IndexColumn::from("users.id")],
+                        index_type: None
+                    },
+                    TableKey::UniqueKey {
+                        name: Some(String::from("email_k")),
+                        columns: vec![IndexColumn {
+                            length: Some(10),
+                            ..IndexColumn::from("users.email")
+                        }],
+                        comment: None,
+                        index_type: None
+                    },
+                ]),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn partitioned_table() {
+        let qstring = "CREATE TABLE logs (id int, created date) ENGINE=InnoDB
+                       PARTITION BY RANGE (YEAR(created)) (
+                       PARTITION p0 VALUES LESS THAN (2020),
+                       PARTITION p1 VALUES LESS THAN MAXVALUE);";
+
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert!(res.partition.is_some());
+        assert!(res.has_unsupported_features());
+
+        let qstring = "CREATE TABLE logs (id int, created date) ENGINE=InnoDB;";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.partition, None);
+        assert!(!res.has_unsupported_features());
+    }
+
+    #[test]
+    fn json_and_spatial_functional_defaults() {
+        let qstring = "CREATE TABLE places (
+                       tags json DEFAULT (JSON_ARRAY()),
+                       loc point NOT NULL DEFAULT (ST_GeomFromText('POINT(0 0)')),
+                       area geometry DEFAULT(NULL));";
+
+        let res = creation(qstring.as_bytes());
+        assert_eq!(
+            res.unwrap().1,
+            CreateTableStatement {
+                table: Table::from("places"),
+                fields: vec![
+                    ColumnSpecification::with_constraints(
+                        Column::from("places.tags"),
+                        SqlType::Json,
+                        vec![ColumnConstraint::DefaultExpression(String::from(
+                            "JSON_ARRAY()"
+                        ))],
+                    ),
+                    ColumnSpecification::with_constraints(
+                        Column::from("places.loc"),
+                        SqlType::Point,
+                        vec![
+                            ColumnConstraint::NotNull,
+                            ColumnConstraint::DefaultExpression(String::from(
+                                "ST_GeomFromText('POINT(0 0)')"
+                            )),
+                        ],
+                    ),
+                    ColumnSpecification::with_constraints(
+                        Column::from("places.area"),
+                        SqlType::Geometry,
+                        vec![ColumnConstraint::DefaultExpression(String::from("NULL"))],
+                    ),
+                ],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parenthesized_expression_defaults() {
+        let qstring = "CREATE TABLE events (
+                       id binary(16) DEFAULT (UUID()),
+                       total int DEFAULT (a + b),
+                       created DATETIME DEFAULT (CURRENT_TIMESTAMP))";
+
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.fields
+                .iter()
+                .map(|f| f.constraints.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                vec![ColumnConstraint::DefaultExpression(String::from("UUID()"))],
+                vec![ColumnConstraint::DefaultExpression(String::from("a + b"))],
+                vec![ColumnConstraint::DefaultExpression(String::from(
+                    "CURRENT_TIMESTAMP"
+                ))],
+            ]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "CREATE TABLE events (id BINARY(16) DEFAULT (UUID()), \
+             total INT DEFAULT (a + b), created DATETIME(0) DEFAULT (CURRENT_TIMESTAMP))"
+        );
+    }
+
     #[test]
     fn mediawiki_create() {
         let qstring = "CREATE TABLE user_newtalk (  user_id int(5) NOT NULL default '0',  user_ip \
@@ -556,7 +2454,7 @@ mod tests {
                 fields: vec![
                     ColumnSpecification::with_constraints(
                         Column::from("user_newtalk.user_id"),
-                        SqlType::Int(5),
+                        SqlType::Int(Some(5)),
                         vec![
                             ColumnConstraint::NotNull,
                             ColumnConstraint::DefaultValue(Literal::String(String::from("0"))),
@@ -643,11 +2541,14 @@ mod tests {
             CreateTableStatement {
                 table: Table::from("users"),
                 fields: vec![
-                    ColumnSpecification::new(Column::from("users.id"), SqlType::Bigint(20)),
+                    ColumnSpecification::new(Column::from("users.id"), SqlType::Bigint(Some(20))),
                     ColumnSpecification::new(Column::from("users.name"), SqlType::Varchar(255)),
                     ColumnSpecification::new(Column::from("users.email"), SqlType::Varchar(255)),
                 ],
-                keys: Some(vec![TableKey::PrimaryKey(vec![Column::from("users.id")])]),
+                keys: Some(vec![TableKey::PrimaryKey {
+                    columns: vec![IndexColumn::from("users.id")],
+                    index_type: None
+                }]),
                 ..Default::default()
             }
         );
@@ -662,14 +2563,16 @@ mod tests {
             CreateTableStatement {
                 table: Table::from("users"),
                 fields: vec![
-                    ColumnSpecification::new(Column::from("users.id"), SqlType::Bigint(20)),
+                    ColumnSpecification::new(Column::from("users.id"), SqlType::Bigint(Some(20))),
                     ColumnSpecification::new(Column::from("users.name"), SqlType::Varchar(255)),
                     ColumnSpecification::new(Column::from("users.email"), SqlType::Varchar(255)),
                 ],
-                keys: Some(vec![TableKey::UniqueKey(
-                    Some(String::from("id_k")),
-                    vec![Column::from("users.id")],
-                ),]),
+                keys: Some(vec![TableKey::UniqueKey {
+                    name: Some(String::from("id_k")),
+                    columns: vec![IndexColumn::from("users.id")],
+                    comment: None,
+                    index_type: None
+                },]),
                 ..Default::default()
             }
         );
@@ -694,7 +2597,7 @@ mod tests {
                 fields: vec![
                     ColumnSpecification::with_constraints(
                         Column::from("django_admin_log.id"),
-                        SqlType::Int(32),
+                        SqlType::Int(None),
                         vec![
                             ColumnConstraint::AutoIncrement,
                             ColumnConstraint::NotNull,
@@ -708,12 +2611,12 @@ mod tests {
                     ),
                     ColumnSpecification::with_constraints(
                         Column::from("django_admin_log.user_id"),
-                        SqlType::Int(32),
+                        SqlType::Int(None),
                         vec![ColumnConstraint::NotNull],
                     ),
                     ColumnSpecification::new(
                         Column::from("django_admin_log.content_type_id"),
-                        SqlType::Int(32),
+                        SqlType::Int(None),
                     ),
                     ColumnSpecification::new(
                         Column::from("django_admin_log.object_id"),
@@ -726,7 +2629,7 @@ mod tests {
                     ),
                     ColumnSpecification::with_constraints(
                         Column::from("django_admin_log.action_flag"),
-                        SqlType::UnsignedInt(32),
+                        SqlType::UnsignedInt(None),
                         vec![ColumnConstraint::NotNull],
                     ),
                     ColumnSpecification::with_constraints(
@@ -735,6 +2638,10 @@ mod tests {
                         vec![ColumnConstraint::NotNull],
                     ),
                 ],
+                keys: Some(vec![TableKey::PrimaryKey {
+                    columns: vec![IndexColumn::from("django_admin_log.id")],
+                    index_type: None
+                }]),
                 ..Default::default()
             }
         );
@@ -750,7 +2657,7 @@ mod tests {
                 fields: vec![
                     ColumnSpecification::with_constraints(
                         Column::from("auth_group.id"),
-                        SqlType::Int(32),
+                        SqlType::Int(None),
                         vec![
                             ColumnConstraint::AutoIncrement,
                             ColumnConstraint::NotNull,
@@ -763,6 +2670,10 @@ mod tests {
                         vec![ColumnConstraint::NotNull, ColumnConstraint::Unique],
                     ),
                 ],
+                keys: Some(vec![TableKey::PrimaryKey {
+                    columns: vec![IndexColumn::from("auth_group.id")],
+                    index_type: None
+                }]),
                 ..Default::default()
             }
         );
@@ -775,7 +2686,7 @@ mod tests {
                        `name` varchar(80) NOT NULL UNIQUE)";
         // TODO(malte): INTEGER isn't quite reflected right here, perhaps
         let expected = "CREATE TABLE auth_group (\
-                        id INT(32) AUTO_INCREMENT NOT NULL PRIMARY KEY, \
+                        id INT AUTO_INCREMENT NOT NULL PRIMARY KEY, \
                         name VARCHAR(80) NOT NULL UNIQUE)";
         let res = creation(qstring.as_bytes());
         assert_eq!(format!("{}", res.unwrap().1), expected);
@@ -808,6 +2719,7 @@ mod tests {
                     })),
                     ..Default::default()
                 })),
+                check_option: None,
             }
         );
     }
@@ -847,6 +2759,7 @@ mod tests {
                     order: None,
                     limit: None,
                 })),
+                check_option: None,
             }
         );
     }
@@ -859,6 +2772,26 @@ mod tests {
         assert_eq!(format!("{}", res.unwrap().1), expected);
     }
 
+    #[test]
+    fn create_view_with_check_option() {
+        let qstring = "CREATE VIEW v AS SELECT * FROM t WHERE x > 1 WITH CASCADED CHECK OPTION;";
+        let res = view_creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.check_option, Some(ViewCheckOption::Cascaded));
+        assert_eq!(
+            format!("{}", res),
+            "CREATE VIEW v AS SELECT * FROM t WHERE x > 1 WITH CASCADED CHECK OPTION"
+        );
+
+        let res = view_creation(b"CREATE VIEW v AS SELECT * FROM t WITH LOCAL CHECK OPTION")
+            .unwrap()
+            .1;
+        assert_eq!(res.check_option, Some(ViewCheckOption::Local));
+        let res = view_creation(b"CREATE VIEW v AS SELECT * FROM t WITH CHECK OPTION")
+            .unwrap()
+            .1;
+        assert_eq!(res.check_option, Some(ViewCheckOption::Cascaded));
+    }
+
     #[test]
     fn lobsters_indexes() {
         let qstring = "CREATE TABLE `comments` (
@@ -879,42 +2812,355 @@ mod tests {
                 fields: vec![
                     ColumnSpecification::with_constraints(
                         Column::from("comments.id"),
-                        SqlType::UnsignedInt(32),
+                        SqlType::UnsignedInt(None),
                         vec![
                             ColumnConstraint::NotNull,
                             ColumnConstraint::AutoIncrement,
                             ColumnConstraint::PrimaryKey,
                         ],
                     ),
-                    ColumnSpecification::new(Column::from("comments.hat_id"), SqlType::Int(32),),
+                    ColumnSpecification::new(Column::from("comments.hat_id"), SqlType::Int(None),),
                 ],
                 keys: Some(vec![
-                    TableKey::FulltextKey(
-                        Some("index_comments_on_comment".into()),
-                        vec![Column::from("comments.comment")]
-                    ),
-                    TableKey::Key(
-                        "confidence_idx".into(),
-                        vec![Column::from("comments.confidence")]
-                    ),
-                    TableKey::UniqueKey(
-                        Some("short_id".into()),
-                        vec![Column::from("comments.short_id")]
-                    ),
-                    TableKey::Key(
-                        "story_id_short_id".into(),
-                        vec![
-                            Column::from("comments.story_id"),
-                            Column::from("comments.short_id")
-                        ]
-                    ),
-                    TableKey::Key("thread_id".into(), vec![Column::from("comments.thread_id")]),
-                    TableKey::Key(
-                        "index_comments_on_user_id".into(),
-                        vec![Column::from("comments.user_id")]
-                    ),
+                    TableKey::PrimaryKey {
+                        columns: vec![IndexColumn::from("comments.id")],
+                        index_type: None
+                    },
+                    TableKey::FulltextKey {
+                        name: Some("index_comments_on_comment".into()),
+                        columns: vec![IndexColumn::from("comments.comment")],
+                        comment: None
+                    },
+                    TableKey::Key {
+                        name: "confidence_idx".into(),
+                        columns: vec![IndexColumn::from("comments.confidence")],
+                        comment: None,
+                        index_type: None
+                    },
+                    TableKey::UniqueKey {
+                        name: Some("short_id".into()),
+                        columns: vec![IndexColumn::from("comments.short_id")],
+                        comment: None,
+                        index_type: None
+                    },
+                    TableKey::Key {
+                        name: "story_id_short_id".into(),
+                        columns: vec![
+                            IndexColumn::from("comments.story_id"),
+                            IndexColumn::from("comments.short_id")
+                        ],
+                        comment: None,
+                        index_type: None
+                    },
+                    TableKey::Key {
+                        name: "thread_id".into(),
+                        columns: vec![IndexColumn::from("comments.thread_id")],
+                        comment: None,
+                        index_type: None
+                    },
+                    TableKey::Key {
+                        name: "index_comments_on_user_id".into(),
+                        columns: vec![IndexColumn::from("comments.user_id")],
+                        comment: None,
+                        index_type: None
+                    },
                 ]),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn create_index() {
+        let qstring = "CREATE UNIQUE INDEX email_idx ON users (email, name);";
+        let res = index_creation(qstring.as_bytes());
+        assert_eq!(
+            res.unwrap().1,
+            CreateIndexStatement {
+                name: String::from("email_idx"),
+                table: Table::from("users"),
+                columns: vec!["users.email".into(), "users.name".into()],
+                unique: true,
+                fulltext: false,
+                nulls_not_distinct: false,
+                comment: None,
             }
         );
+
+        let qstring = "create index name_idx on users(name)";
+        let res = index_creation(qstring.as_bytes());
+        assert_eq!(
+            format!("{}", res.unwrap().1),
+            "CREATE INDEX name_idx ON users (name)"
+        );
+    }
+
+    #[test]
+    fn create_unique_index_nulls_not_distinct() {
+        let qstring = "CREATE UNIQUE INDEX email_idx ON users (email) NULLS NOT DISTINCT;";
+        let res = index_creation(qstring.as_bytes()).unwrap().1;
+        assert!(res.unique);
+        assert!(res.nulls_not_distinct);
+        assert_eq!(
+            format!("{}", res),
+            "CREATE UNIQUE INDEX email_idx ON users (email) NULLS NOT DISTINCT"
+        );
+
+        let qstring = "CREATE UNIQUE INDEX email_idx ON users (email);";
+        let res = index_creation(qstring.as_bytes()).unwrap().1;
+        assert!(!res.nulls_not_distinct);
+    }
+
+    #[test]
+    fn create_index_comment() {
+        let qstring = "CREATE INDEX idx ON t (a) COMMENT 'lookup by a';";
+        let res = index_creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.comment, Some(String::from("lookup by a")));
+        assert_eq!(
+            format!("{}", res),
+            "CREATE INDEX idx ON t (a) COMMENT 'lookup by a'"
+        );
+
+        let qstring = "CREATE INDEX idx ON t (a) COMMENT 'a''s index'";
+        let res = index_creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.comment, Some(String::from("a's index")));
+        assert_eq!(format!("{}", res), qstring);
+    }
+
+    #[test]
+    fn lenient_constraints_before_type() {
+        let (_, spec) = column_specification_lenient(b"id NOT NULL bigint(20)").unwrap();
+        assert_eq!(
+            spec,
+            ColumnSpecification::with_constraints(
+                Column::from("id"),
+                SqlType::Bigint(Some(20)),
+                vec![ColumnConstraint::NotNull],
+            )
+        );
+
+        let (_, spec) = column_specification_lenient(b"n DEFAULT 0 int unsigned NOT NULL").unwrap();
+        assert_eq!(spec.sql_type, SqlType::UnsignedInt(None));
+        assert_eq!(
+            spec.constraints,
+            vec![
+                ColumnConstraint::DefaultValue(Literal::Integer(0)),
+                ColumnConstraint::NotNull,
+            ]
+        );
+
+        let input = b"id NOT NULL";
+        let ((_, spec), warnings) =
+            warning::collect_warnings(input, || column_specification_lenient(input).unwrap());
+        assert_eq!(spec.sql_type, SqlType::Text);
+        assert_eq!(warnings.len(), 1);
+
+        // the strict parser does not reorder
+        let qstring = "CREATE TABLE t (id NOT NULL bigint(20))";
+        assert!(creation(qstring.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn signed_default_on_unsigned_column() {
+        let (_, spec) = column_specification(b"x int unsigned DEFAULT -1").unwrap();
+        assert_eq!(
+            spec.constraints,
+            vec![ColumnConstraint::DefaultValue(Literal::Integer(-1))]
+        );
+        assert!(spec.validate().is_err());
+
+        let (_, spec) = column_specification(b"x int DEFAULT -1").unwrap();
+        assert_eq!(spec.validate(), Ok(()));
+        let (_, spec) = column_specification(b"x int unsigned DEFAULT 1").unwrap();
+        assert_eq!(spec.validate(), Ok(()));
+    }
+
+    #[test]
+    fn normalize_equivalent_tables() {
+        let a = "CREATE TABLE t (id INT(11) NOT NULL, flag BOOL, \
+                 name VARCHAR(10) CHARACTER SET UTF8MB4 DEFAULT NULL, n bigint(20) unsigned)";
+        let b = "create table t (id integer not null, flag tinyint(1), \
+                 name varchar(10) charset utf8mb4, n BIGINT UNSIGNED)";
+        let mut a = creation(a.as_bytes()).unwrap().1;
+        let mut b = creation(b.as_bytes()).unwrap().1;
+        assert_ne!(a, b);
+
+        a.normalize();
+        b.normalize();
+        assert_eq!(a, b);
+        assert_eq!(
+            format!("{}", a),
+            "CREATE TABLE t (id INT NOT NULL, flag TINYINT, \
+             name VARCHAR(10) CHARACTER SET utf8mb4, n BIGINT UNSIGNED)"
+        );
+
+        // a NULL default is meaningful on a NOT NULL column, and widths of other types are kept
+        let mut c = creation(b"CREATE TABLE t (x int NOT NULL DEFAULT NULL, s char(3))")
+            .unwrap()
+            .1;
+        c.normalize();
+        assert_eq!(
+            format!("{}", c),
+            "CREATE TABLE t (x INT NOT NULL DEFAULT NULL, s CHAR(3))"
+        );
+    }
+
+    #[test]
+    fn build_create_table() {
+        let table = CreateTableBuilder::new("users")
+            .column("id", SqlType::UnsignedBigint(Some(20)))
+            .not_null()
+            .auto_increment()
+            .column("email", SqlType::Varchar(255))
+            .not_null()
+            .column("karma", SqlType::Int(Some(11)))
+            .default_value(0)
+            .comment("reputation")
+            .primary_key(&["id"])
+            .unique_key("email_idx", &["email"])
+            .build();
+
+        let sql = format!("{}", table);
+        assert_eq!(
+            sql,
+            "CREATE TABLE users (id BIGINT(20) UNSIGNED NOT NULL AUTO_INCREMENT, \
+             email VARCHAR(255) NOT NULL, karma INT(11) DEFAULT 0 COMMENT 'reputation', \
+             PRIMARY KEY (id), UNIQUE KEY email_idx (email))"
+        );
+        assert_eq!(creation(sql.as_bytes()).unwrap().1, table);
+    }
+
+    #[test]
+    #[should_panic(expected = "no column has been added")]
+    fn build_constraint_without_column() {
+        CreateTableBuilder::new("t").not_null();
+    }
+
+    #[test]
+    fn columns_in_generation_order() {
+        let qstring = "CREATE TABLE t (total int AS (subtotal + tax), \
+                       subtotal int AS (price * qty), price int, qty int, tax int)";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+
+        let order: Vec<_> = res
+            .columns_in_generation_order()
+            .unwrap()
+            .iter()
+            .map(|f| f.column.name.as_str())
+            .collect();
+        assert_eq!(order, vec!["price", "qty", "subtotal", "tax", "total"]);
+
+        let qstring = "CREATE TABLE t (id int, a int AS (b + 1), b int AS (a + 1))";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.columns_in_generation_order(),
+            Err(CycleError {
+                columns: vec![String::from("a"), String::from("b")],
+            })
+        );
+    }
+
+    #[test]
+    fn unique_column_sets() {
+        let qstring = "CREATE TABLE users (id int, org int, email varchar(255), \
+                       name varchar(255), PRIMARY KEY (id), UNIQUE KEY (org, email), KEY name_idx (name))";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+
+        assert_eq!(
+            res.unique_column_sets(),
+            vec![
+                vec![&Column::from("users.id")],
+                vec![&Column::from("users.org"), &Column::from("users.email")],
+            ]
+        );
+    }
+
+    #[test]
+    fn foreign_key_dependencies() {
+        let qstring = "CREATE TABLE posts (id int PRIMARY KEY, \
+                       author_id int REFERENCES users (id), editor_id int, org_id int, \
+                       FOREIGN KEY (editor_id) REFERENCES users (id), \
+                       CONSTRAINT fk_org FOREIGN KEY (org_id) REFERENCES orgs (id))";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+
+        assert_eq!(
+            res.foreign_key_dependencies(),
+            vec![&Table::from("users"), &Table::from("orgs")]
+        );
+
+        let res = creation(b"CREATE TABLE t (id int PRIMARY KEY)").unwrap().1;
+        assert!(res.foreign_key_dependencies().is_empty());
+    }
+
+    #[test]
+    fn keys_as_alter_adds() {
+        let qstring = "CREATE TABLE users (id int, email varchar(255), \
+                       PRIMARY KEY (id), UNIQUE KEY email_idx (email))";
+        let res = creation(qstring.as_bytes()).unwrap().1;
+
+        let alters: Vec<_> = res
+            .keys_as_alter_adds()
+            .iter()
+            .map(|a| format!("{}", a))
+            .collect();
+        assert_eq!(
+            alters,
+            vec![
+                "ALTER TABLE users ADD PRIMARY KEY (id)",
+                "ALTER TABLE users ADD UNIQUE KEY email_idx (email)",
+            ]
+        );
+    }
+
+    #[test]
+    fn separate_and_inline_keys() {
+        let qstring = "CREATE TABLE users (id int, email varchar(255), name varchar(255), \
+                       PRIMARY KEY (id), UNIQUE KEY email_idx (email), KEY name_idx (name))";
+        let original = creation(qstring.as_bytes()).unwrap().1;
+
+        let (table, indexes) = original.clone().with_separate_indexes();
+        assert_eq!(
+            table.keys,
+            Some(vec![TableKey::PrimaryKey {
+                columns: vec!["users.id".into()],
+                index_type: None
+            }])
+        );
+        let formatted: Vec<_> = indexes.iter().map(|i| format!("{}", i)).collect();
+        assert_eq!(
+            formatted,
+            vec![
+                "CREATE UNIQUE INDEX email_idx ON users (email)",
+                "CREATE INDEX name_idx ON users (name)",
+            ]
+        );
+        for (index, sql) in indexes.iter().zip(formatted.iter()) {
+            assert_eq!(index_creation(sql.as_bytes()).unwrap().1, *index);
+        }
+
+        assert_eq!(table.with_inline_keys(indexes), original);
+    }
+
+    #[test]
+    fn create_database_charset_and_collation() {
+        let expected = CreateDatabaseStatement {
+            name: String::from("app"),
+            charset: Some(String::from("utf8mb4")),
+            collation: Some(String::from("utf8mb4_unicode_ci")),
+        };
+
+        let qstring =
+            "CREATE DATABASE app DEFAULT CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci;";
+        let res = database_creation(qstring.as_bytes());
+        assert_eq!(res.unwrap().1, expected);
+
+        let qstring = "create schema app collate = utf8mb4_unicode_ci charset utf8mb4";
+        let res = database_creation(qstring.as_bytes());
+        assert_eq!(res.unwrap().1, expected);
+
+        assert_eq!(
+            format!("{}", expected),
+            "CREATE DATABASE app DEFAULT CHARACTER SET utf8mb4 DEFAULT COLLATE utf8mb4_unicode_ci"
+        );
     }
 }
@@ -1,18 +1,68 @@
-use nom::character::complete::{alphanumeric1, multispace0, multispace1};
+use nom::character::complete::{alphanumeric1, digit1, multispace0, multispace1};
+use std::fmt;
+use std::str;
 
-use common::{integer_literal, sql_identifier, string_literal, ws_sep_comma, ws_sep_equals};
+use common::{
+    charset_keyword, integer_literal, sql_identifier, string_literal, ws_sep_comma, ws_sep_equals,
+};
 use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
-use nom::combinator::{map, opt};
+use nom::combinator::{map, map_res, opt};
 use nom::multi::separated_list0;
-use nom::sequence::tuple;
+use nom::sequence::{terminated, tuple};
 use nom::IResult;
+use warning;
 
-pub fn table_options(i: &[u8]) -> IResult<&[u8], ()> {
-    // TODO: make the create options accessible
+/// A table option kept in the AST. Other table options are recognized, but discarded.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum TableOption {
+    /// `AUTO_INCREMENT=n`, the value the table's auto-increment counter starts from.
+    AutoIncrement(u64),
+    /// `ROW_FORMAT=...`, how InnoDB and MyISAM store the table's rows.
+    RowFormat(RowFormat),
+    /// `KEY_BLOCK_SIZE=n`, the page size in kilobytes of a compressed InnoDB table.
+    KeyBlockSize(u64),
+}
+
+impl fmt::Display for TableOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TableOption::AutoIncrement(start) => write!(f, "AUTO_INCREMENT={}", start),
+            TableOption::RowFormat(format) => write!(f, "ROW_FORMAT={}", format),
+            TableOption::KeyBlockSize(size) => write!(f, "KEY_BLOCK_SIZE={}", size),
+        }
+    }
+}
+
+/// The row format of a `ROW_FORMAT` table option.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum RowFormat {
+    Default,
+    Dynamic,
+    Fixed,
+    Compressed,
+    Redundant,
+    Compact,
+}
+
+impl fmt::Display for RowFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RowFormat::Default => write!(f, "DEFAULT"),
+            RowFormat::Dynamic => write!(f, "DYNAMIC"),
+            RowFormat::Fixed => write!(f, "FIXED"),
+            RowFormat::Compressed => write!(f, "COMPRESSED"),
+            RowFormat::Redundant => write!(f, "REDUNDANT"),
+            RowFormat::Compact => write!(f, "COMPACT"),
+        }
+    }
+}
+
+pub fn table_options(i: &[u8]) -> IResult<&[u8], Vec<TableOption>> {
+    // TODO: make the remaining create options accessible
     map(
         separated_list0(table_options_separator, create_option),
-        |_| (),
+        |options| options.into_iter().flatten().collect(),
     )(i)
 }
 
@@ -20,20 +70,35 @@ fn table_options_separator(i: &[u8]) -> IResult<&[u8], ()> {
     map(alt((multispace1, ws_sep_comma)), |_| ())(i)
 }
 
-fn create_option(i: &[u8]) -> IResult<&[u8], ()> {
+fn create_option(i: &[u8]) -> IResult<&[u8], Option<TableOption>> {
     alt((
+        map(create_option_auto_increment, Some),
+        map(create_option_row_format, Some),
+        map(create_option_key_block_size, Some),
+        discarded_create_option,
+    ))(i)
+}
+
+fn discarded_create_option(i: &[u8]) -> IResult<&[u8], Option<TableOption>> {
+    let (remaining_input, _) = alt((
         create_option_type,
         create_option_pack_keys,
         create_option_engine,
-        create_option_auto_increment,
         create_option_default_charset,
         create_option_collate,
         create_option_comment,
         create_option_max_rows,
         create_option_avg_row_length,
-        create_option_row_format,
-        create_option_key_block_size,
-    ))(i)
+    ))(i)?;
+
+    let option = &i[..i.len() - remaining_input.len()];
+    warning::warn(i, || {
+        format!(
+            "table option `{}` is not represented",
+            String::from_utf8_lossy(option)
+        )
+    });
+    Ok((remaining_input, None))
 }
 
 /// Helper to parse equals-separated create option pairs.
@@ -68,13 +133,21 @@ fn create_option_engine(i: &[u8]) -> IResult<&[u8], ()> {
     create_option_equals_pair(tag_no_case("engine"), opt(alphanumeric1))(i)
 }
 
-fn create_option_auto_increment(i: &[u8]) -> IResult<&[u8], ()> {
-    create_option_equals_pair(tag_no_case("auto_increment"), integer_literal)(i)
+fn create_option_auto_increment(i: &[u8]) -> IResult<&[u8], TableOption> {
+    let (remaining_input, (_, _, start)) = tuple((
+        tag_no_case("auto_increment"),
+        ws_sep_equals,
+        map_res(digit1, |d| str::from_utf8(d).unwrap().parse::<u64>()),
+    ))(i)?;
+    Ok((remaining_input, TableOption::AutoIncrement(start)))
 }
 
 fn create_option_default_charset(i: &[u8]) -> IResult<&[u8], ()> {
-    create_option_equals_pair(
-        tag_no_case("default charset"),
+    let (remaining_input, (_, _, _, _)) = tuple((
+        opt(terminated(tag_no_case("default"), multispace1)),
+        charset_keyword,
+        // MySQL accepts the value separated by either `=` or plain whitespace
+        alt((ws_sep_equals, multispace1)),
         alt((
             tag("utf8mb4"),
             tag("utf8"),
@@ -83,7 +156,8 @@ fn create_option_default_charset(i: &[u8]) -> IResult<&[u8], ()> {
             tag("ucs2"),
             tag("latin1"),
         )),
-    )(i)
+    ))(i)?;
+    Ok((remaining_input, ()))
 }
 
 fn create_option_collate(i: &[u8]) -> IResult<&[u8], ()> {
@@ -106,33 +180,33 @@ fn create_option_avg_row_length(i: &[u8]) -> IResult<&[u8], ()> {
     create_option_equals_pair(tag_no_case("avg_row_length"), integer_literal)(i)
 }
 
-fn create_option_row_format(i: &[u8]) -> IResult<&[u8], ()> {
-    let (remaining_input, (_, _, _, _, _)) = tuple((
+fn create_option_row_format(i: &[u8]) -> IResult<&[u8], TableOption> {
+    let (remaining_input, (_, _, _, _, format)) = tuple((
         tag_no_case("row_format"),
         multispace0,
         opt(tag("=")),
         multispace0,
         alt((
-            tag_no_case("DEFAULT"),
-            tag_no_case("DYNAMIC"),
-            tag_no_case("FIXED"),
-            tag_no_case("COMPRESSED"),
-            tag_no_case("REDUNDANT"),
-            tag_no_case("COMPACT"),
+            map(tag_no_case("DEFAULT"), |_| RowFormat::Default),
+            map(tag_no_case("DYNAMIC"), |_| RowFormat::Dynamic),
+            map(tag_no_case("FIXED"), |_| RowFormat::Fixed),
+            map(tag_no_case("COMPRESSED"), |_| RowFormat::Compressed),
+            map(tag_no_case("REDUNDANT"), |_| RowFormat::Redundant),
+            map(tag_no_case("COMPACT"), |_| RowFormat::Compact),
         )),
     ))(i)?;
-    Ok((remaining_input, ()))
+    Ok((remaining_input, TableOption::RowFormat(format)))
 }
 
-fn create_option_key_block_size(i: &[u8]) -> IResult<&[u8], ()> {
-    let (remaining_input, (_, _, _, _, _)) = tuple((
+fn create_option_key_block_size(i: &[u8]) -> IResult<&[u8], TableOption> {
+    let (remaining_input, (_, _, _, _, size)) = tuple((
         tag_no_case("key_block_size"),
         multispace0,
         opt(tag("=")),
         multispace0,
-        integer_literal,
+        map_res(digit1, |d| str::from_utf8(d).unwrap().parse::<u64>()),
     ))(i)?;
-    Ok((remaining_input, ()))
+    Ok((remaining_input, TableOption::KeyBlockSize(size)))
 }
 
 #[cfg(test)]
@@ -140,7 +214,10 @@ mod tests {
     use super::*;
 
     fn should_parse_all(qstring: &str) {
-        assert_eq!(Ok((&b""[..], ())), table_options(qstring.as_bytes()))
+        assert_eq!(
+            Ok(&b""[..]),
+            table_options(qstring.as_bytes()).map(|(rest, _)| rest)
+        )
     }
 
     #[test]
@@ -156,6 +233,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_table_option_charset_forms() {
+        should_parse_all("ENGINE=InnoDB CHARSET=utf8mb4");
+        should_parse_all("ENGINE=InnoDB DEFAULT CHARACTER SET = utf8mb4");
+        should_parse_all("CHARACTER SET utf8 COLLATE=utf8_bin");
+    }
+
+    #[test]
+    fn create_table_option_default_charset_separators() {
+        should_parse_all("ENGINE=InnoDB DEFAULT CHARSET=utf8mb4");
+        should_parse_all("ENGINE=InnoDB DEFAULT CHARSET utf8mb4");
+        should_parse_all("ENGINE=InnoDB DEFAULT CHARSET = utf8mb4");
+    }
+
+    #[test]
+    fn create_table_option_auto_increment() {
+        let res = table_options(b"ENGINE=InnoDB AUTO_INCREMENT=1000 DEFAULT CHARSET=utf8mb4");
+        assert_eq!(res, Ok((&b""[..], vec![TableOption::AutoIncrement(1000)])));
+        assert_eq!(
+            format!("{}", TableOption::AutoIncrement(1000)),
+            "AUTO_INCREMENT=1000"
+        );
+    }
+
+    #[test]
+    fn create_table_option_row_format_and_key_block_size() {
+        let res = table_options(b"ENGINE=InnoDB ROW_FORMAT=DYNAMIC KEY_BLOCK_SIZE=8");
+        assert_eq!(
+            res,
+            Ok((
+                &b""[..],
+                vec![
+                    TableOption::RowFormat(RowFormat::Dynamic),
+                    TableOption::KeyBlockSize(8),
+                ]
+            ))
+        );
+
+        let res = table_options(b"row_format compressed, key_block_size = 4");
+        assert_eq!(
+            res,
+            Ok((
+                &b""[..],
+                vec![
+                    TableOption::RowFormat(RowFormat::Compressed),
+                    TableOption::KeyBlockSize(4),
+                ]
+            ))
+        );
+    }
+
     #[test]
     fn create_table_option_list_commaseparated() {
         should_parse_all("AUTO_INCREMENT=1,ENGINE=,KEY_BLOCK_SIZE=8");
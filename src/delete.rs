@@ -3,7 +3,6 @@ use std::{fmt, str};
 
 use common::{statement_terminator, schema_table_reference};
 use condition::ConditionExpression;
-use keywords::escape_if_keyword;
 use nom::bytes::complete::tag_no_case;
 use nom::combinator::opt;
 use nom::sequence::{delimited, tuple};
@@ -20,7 +19,7 @@ pub struct DeleteStatement {
 impl fmt::Display for DeleteStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "DELETE FROM ")?;
-        write!(f, "{}", escape_if_keyword(&self.table.name))?;
+        write!(f, "{}", self.table)?;
         if let Some(ref where_clause) = self.where_clause {
             write!(f, " WHERE ")?;
             write!(f, "{}", where_clause)?;
@@ -110,4 +109,16 @@ mod tests {
         let res = deletion(qstring.as_bytes());
         assert_eq!(format!("{}", res.unwrap().1), expected);
     }
+
+    #[test]
+    fn delete_without_terminator() {
+        let res = deletion(b"DELETE FROM t").unwrap().1;
+        assert_eq!(res.table, Table::from("t"));
+        assert_eq!(res.where_clause, None);
+
+        let res = deletion(b"DELETE FROM db1.t WHERE id = 3").unwrap().1;
+        assert_eq!(res.table, Table::from(("db1", "t")));
+        assert!(res.where_clause.is_some());
+        assert_eq!(format!("{}", res), "DELETE FROM db1.t WHERE id = 3");
+    }
 }
@@ -0,0 +1,47 @@
+use context;
+use nom::error::{ErrorKind, ParseError};
+use nom::IResult;
+
+/// A SQL dialect whose rules take precedence where dialects disagree, such as on identifier
+/// quoting or the meaning of `SERIAL`. Parsing without a dialect (e.g. through `parse_query`) is
+/// permissive and leans towards MySQL.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Dialect {
+    /// Standard SQL: identifiers are quoted with double quotes only, and `'...'` is the only
+    /// string syntax.
+    Ansi,
+    /// Identifiers are quoted with backticks, `"..."` is a string, `BOOL` is `TINYINT(1)`.
+    MySQL,
+    /// Identifiers are quoted with double quotes; `SERIAL` and `BIGSERIAL` are auto-incrementing
+    /// `INT` and `BIGINT`; there is no `ON DUPLICATE KEY UPDATE`.
+    PostgreSQL,
+    /// Identifiers may be quoted with double quotes, backticks or brackets; there is no
+    /// `ON DUPLICATE KEY UPDATE`.
+    SQLite,
+}
+
+/// The dialect being parsed on this thread, or `None` in the default permissive mode.
+pub(crate) fn current() -> Option<Dialect> {
+    context::with(|c| c.dialect)
+}
+
+/// Runs `f` with `dialect` as the current dialect on this thread.
+pub(crate) fn with_dialect<T, F: FnOnce() -> T>(dialect: Dialect, f: F) -> T {
+    context::scoped(|c| &mut c.dialect, Some(dialect), f).0
+}
+
+// Parser that consumes nothing, and fails unless `allowed` holds for the current dialect.
+pub(crate) fn dialect_allows(
+    allowed: fn(Option<Dialect>) -> bool,
+) -> impl Fn(&[u8]) -> IResult<&[u8], ()> {
+    move |i| {
+        if allowed(current()) {
+            Ok((i, ()))
+        } else {
+            Err(nom::Err::Error(ParseError::from_error_kind(
+                i,
+                ErrorKind::Verify,
+            )))
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use nom::character::complete::multispace0;
+use nom::character::complete::{multispace0, multispace1};
 use std::{fmt, str};
 
-use common::{statement_terminator, table_list};
+use common::{qualified_table_name, sql_identifier, statement_terminator, table_list};
 use keywords::escape_if_keyword;
 use nom::bytes::complete::tag_no_case;
 use nom::combinator::opt;
-use nom::sequence::{delimited, tuple};
+use nom::sequence::{delimited, preceded, tuple};
 use nom::IResult;
 use table::Table;
 
@@ -61,6 +61,48 @@ pub fn drop_table(i: &[u8]) -> IResult<&[u8], DropTableStatement> {
     ))
 }
 
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DropIndexStatement {
+    pub name: String,
+    /// The table the index belongs to; MySQL requires it (`DROP INDEX idx ON t`), while standard
+    /// SQL names indexes per schema and leaves it out.
+    pub table: Option<Table>,
+}
+
+impl fmt::Display for DropIndexStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DROP INDEX {}", escape_if_keyword(&self.name))?;
+        if let Some(ref table) = self.table {
+            write!(f, " ON {}", table)?;
+        }
+        Ok(())
+    }
+}
+
+// Parse rule for a SQL DROP INDEX query, with or without MySQL's `ON table`.
+pub fn drop_index(i: &[u8]) -> IResult<&[u8], DropIndexStatement> {
+    let (remaining_input, (_, _, _, _, name, table, _)) = tuple((
+        tag_no_case("drop"),
+        multispace1,
+        tag_no_case("index"),
+        multispace1,
+        sql_identifier,
+        opt(preceded(
+            tuple((multispace1, tag_no_case("on"), multispace1)),
+            qualified_table_name,
+        )),
+        statement_terminator,
+    ))(i)?;
+
+    Ok((
+        remaining_input,
+        DropIndexStatement {
+            name: String::from_utf8(name.to_vec()).unwrap(),
+            table,
+        },
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -86,4 +128,32 @@ mod tests {
         let res = drop_table(qstring.as_bytes());
         assert_eq!(format!("{}", res.unwrap().1), expected);
     }
+
+    #[test]
+    fn drop_index_on_table() {
+        let qstring = "DROP INDEX idx_name ON users;";
+        let res = drop_index(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res,
+            DropIndexStatement {
+                name: String::from("idx_name"),
+                table: Some(Table::from("users")),
+            }
+        );
+        assert_eq!(format!("{}", res), "DROP INDEX idx_name ON users");
+    }
+
+    #[test]
+    fn drop_index_without_table() {
+        let qstring = "drop index idx_name";
+        let res = drop_index(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res,
+            DropIndexStatement {
+                name: String::from("idx_name"),
+                table: None,
+            }
+        );
+        assert_eq!(format!("{}", res), "DROP INDEX idx_name");
+    }
 }
@@ -4,29 +4,122 @@ use std::str;
 
 use column::Column;
 use common::{
-    assignment_expr_list, field_list, statement_terminator, schema_table_reference, value_list,
-    ws_sep_comma, FieldValueExpression, Literal,
+    assignment_expr_list, field_list, is_sql_identifier, literal, schema_table_reference,
+    statement_terminator, ws_sep_comma, FieldValueExpression, Literal,
 };
+use dialect::{dialect_allows, Dialect};
 use keywords::escape_if_keyword;
-use nom::bytes::complete::{tag, tag_no_case};
-use nom::combinator::opt;
-use nom::multi::many1;
-use nom::sequence::{delimited, preceded, tuple};
+use nom::branch::alt;
+use nom::bytes::complete::{tag, tag_no_case, take_while1};
+use nom::combinator::{map, not, opt, peek};
+use nom::multi::{many0, separated_list1};
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::IResult;
+use select::{nested_selection, SelectStatement};
 use table::Table;
 
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct InsertStatement {
     pub table: Table,
     pub fields: Option<Vec<Column>>,
-    pub data: Vec<Vec<Literal>>,
+    pub data: InsertData,
     pub ignore: bool,
+    /// Whether this is a MySQL `REPLACE` statement, which deletes conflicting rows first.
+    pub replace: bool,
     pub on_duplicate: Option<Vec<(Column, FieldValueExpression)>>,
+    /// The `ON CONFLICT` upsert clause of SQLite and PostgreSQL.
+    pub on_conflict: Option<OnConflict>,
+}
+
+/// An `ON CONFLICT [(columns)] DO ...` clause.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct OnConflict {
+    /// The columns of the unique constraint whose violation is handled, if given.
+    pub target: Option<Vec<Column>>,
+    pub action: ConflictAction,
+}
+
+/// What an `ON CONFLICT` clause does with a row that violates a unique constraint.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum ConflictAction {
+    /// `DO NOTHING`: the row is skipped.
+    DoNothing,
+    /// `DO UPDATE SET ...`: the existing row is updated instead.
+    DoUpdate(Vec<(Column, FieldValueExpression)>),
+}
+
+impl fmt::Display for OnConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ON CONFLICT")?;
+        if let Some(ref target) = self.target {
+            write!(
+                f,
+                " ({})",
+                target
+                    .iter()
+                    .map(|c| escape_if_keyword(&c.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        match self.action {
+            ConflictAction::DoNothing => write!(f, " DO NOTHING"),
+            ConflictAction::DoUpdate(ref assignments) => write!(
+                f,
+                " DO UPDATE SET {}",
+                assignments
+                    .iter()
+                    .map(|(col, value)| format!("{} = {}", col, value))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// The rows inserted by an INSERT statement.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum InsertData {
+    Values(Vec<Vec<Literal>>),
+    Select(Box<SelectStatement>),
+}
+
+impl Default for InsertData {
+    fn default() -> Self {
+        InsertData::Values(vec![])
+    }
+}
+
+impl fmt::Display for InsertData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InsertData::Values(ref rows) => write!(
+                f,
+                "VALUES {}",
+                rows.iter()
+                    .map(|row| format!(
+                        "({})",
+                        row.iter()
+                            .map(|l| l.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            InsertData::Select(ref select) => write!(f, "{}", select),
+        }
+    }
 }
 
 impl fmt::Display for InsertStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "INSERT INTO {}", escape_if_keyword(&self.table.name))?;
+        write!(
+            f,
+            "{} INTO {}",
+            if self.replace { "REPLACE" } else { "INSERT" },
+            escape_if_keyword(&self.table.name)
+        )?;
         if let Some(ref fields) = self.fields {
             write!(
                 f,
@@ -38,22 +131,22 @@ impl fmt::Display for InsertStatement {
                     .join(", ")
             )?;
         }
-        write!(
-            f,
-            " VALUES {}",
-            self.data
-                .iter()
-                .map(|datas| format!(
-                    "({})",
-                    datas
-                        .into_iter()
-                        .map(|l| l.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                ))
-                .collect::<Vec<_>>()
-                .join(", ")
-        )
+        write!(f, " {}", self.data)?;
+        if let Some(ref on_duplicate) = self.on_duplicate {
+            write!(
+                f,
+                " ON DUPLICATE KEY UPDATE {}",
+                on_duplicate
+                    .iter()
+                    .map(|(col, value)| format!("{} = {}", col, value))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        if let Some(ref on_conflict) = self.on_conflict {
+            write!(f, " {}", on_conflict)?;
+        }
+        Ok(())
     }
 }
 
@@ -61,43 +154,119 @@ fn fields(i: &[u8]) -> IResult<&[u8], Vec<Column>> {
     delimited(
         preceded(tag("("), multispace0),
         field_list,
-        delimited(multispace0, tag(")"), multispace1),
+        delimited(multispace0, tag(")"), multispace0),
     )(i)
 }
 
 fn data(i: &[u8]) -> IResult<&[u8], Vec<Literal>> {
-    delimited(tag("("), value_list, preceded(tag(")"), opt(ws_sep_comma)))(i)
+    delimited(
+        tag("("),
+        many0(delimited(multispace0, insert_value, opt(ws_sep_comma))),
+        preceded(multispace0, tag(")")),
+    )(i)
+}
+
+// Parse rule for a single value in a VALUES row: a literal, or DEFAULT for the column's default.
+fn insert_value(i: &[u8]) -> IResult<&[u8], Literal> {
+    alt((
+        map(
+            terminated(
+                tag_no_case("default"),
+                not(peek(take_while1(is_sql_identifier))),
+            ),
+            |_| Literal::Default,
+        ),
+        literal,
+    ))(i)
+}
+
+// Parse rule for one or more comma-separated value tuples.
+fn data_list(i: &[u8]) -> IResult<&[u8], Vec<Vec<Literal>>> {
+    separated_list1(ws_sep_comma, data)(i)
 }
 
+// Parse rule for MySQL's ON DUPLICATE KEY UPDATE clause.
 fn on_duplicate(i: &[u8]) -> IResult<&[u8], Vec<(Column, FieldValueExpression)>> {
     preceded(
+        tuple((
+            dialect_allows(|d| matches!(d, None | Some(Dialect::MySQL))),
+            multispace0,
+            tag_no_case("on"),
+            multispace1,
+            tag_no_case("duplicate"),
+            multispace1,
+            tag_no_case("key"),
+            multispace1,
+            tag_no_case("update"),
+            multispace1,
+        )),
+        assignment_expr_list,
+    )(i)
+}
+
+// Parse rule for the ON CONFLICT clause of SQLite and PostgreSQL upserts.
+fn on_conflict(i: &[u8]) -> IResult<&[u8], OnConflict> {
+    let (remaining_input, (_, _, _, _, _, target, _, _, action)) = tuple((
+        dialect_allows(|d| matches!(d, None | Some(Dialect::PostgreSQL) | Some(Dialect::SQLite))),
+        multispace0,
+        tag_no_case("on"),
+        multispace1,
+        tag_no_case("conflict"),
+        opt(preceded(multispace0, fields)),
         multispace0,
+        tag_no_case("do"),
         preceded(
-            tag_no_case("on duplicate key update"),
-            preceded(multispace1, assignment_expr_list),
+            multispace1,
+            alt((
+                map(tag_no_case("nothing"), |_| ConflictAction::DoNothing),
+                map(
+                    preceded(
+                        tuple((
+                            tag_no_case("update"),
+                            multispace1,
+                            tag_no_case("set"),
+                            multispace1,
+                        )),
+                        assignment_expr_list,
+                    ),
+                    ConflictAction::DoUpdate,
+                ),
+            )),
         ),
-    )(i)
+    ))(i)?;
+
+    Ok((remaining_input, OnConflict { target, action }))
 }
 
-// Parse rule for a SQL insert query.
-// TODO(malte): support REPLACE, nested selection, DEFAULT VALUES
+// Parse rule for a SQL insert query, or a MySQL REPLACE query.
+// TODO(malte): support DEFAULT VALUES
 pub fn insertion(i: &[u8]) -> IResult<&[u8], InsertStatement> {
-    let (remaining_input, (_, ignore_res, _, _, _, table, _, fields, _, _, data, on_duplicate, _)) =
-        tuple((
-            tag_no_case("insert"),
-            opt(preceded(multispace1, tag_no_case("ignore"))),
-            multispace1,
-            tag_no_case("into"),
-            multispace1,
-            schema_table_reference,
-            multispace0,
-            opt(fields),
-            tag_no_case("values"),
-            multispace0,
-            many1(data),
-            opt(on_duplicate),
-            statement_terminator,
-        ))(i)?;
+    let (
+        remaining_input,
+        (replace, ignore_res, _, _, _, table, _, fields, data, on_duplicate, on_conflict, _),
+    ) = tuple((
+        alt((
+            map(tag_no_case("insert"), |_| false),
+            map(tag_no_case("replace"), |_| true),
+        )),
+        opt(preceded(multispace1, tag_no_case("ignore"))),
+        multispace1,
+        tag_no_case("into"),
+        multispace1,
+        schema_table_reference,
+        multispace0,
+        opt(fields),
+        alt((
+            map(
+                preceded(pair(tag_no_case("values"), multispace0), data_list),
+                InsertData::Values,
+            ),
+            map(nested_selection, |s| InsertData::Select(Box::new(s))),
+        )),
+        opt(on_duplicate),
+        opt(on_conflict),
+        statement_terminator,
+    ))(i)?;
     assert!(table.alias.is_none());
     let ignore = ignore_res.is_some();
 
@@ -108,7 +277,9 @@ pub fn insertion(i: &[u8]) -> IResult<&[u8], InsertStatement> {
             fields,
             data,
             ignore,
+            replace,
             on_duplicate,
+            on_conflict,
         },
     ))
 }
@@ -117,8 +288,8 @@ pub fn insertion(i: &[u8]) -> IResult<&[u8], InsertStatement> {
 mod tests {
     use super::*;
     use arithmetic::{ArithmeticBase, ArithmeticExpression, ArithmeticOperator};
-    use column::Column;
-    use common::ItemPlaceholder;
+    use column::{Column, FunctionArgument, FunctionArguments, FunctionExpression};
+    use common::{FieldDefinitionExpression, ItemPlaceholder, LiteralExpression, Real};
     use table::Table;
 
     #[test]
@@ -131,12 +302,58 @@ mod tests {
             InsertStatement {
                 table: Table::from("users"),
                 fields: None,
-                data: vec![vec![42.into(), "test".into()]],
+                data: InsertData::Values(vec![vec![42.into(), "test".into()]]),
                 ..Default::default()
             }
         );
     }
 
+    #[test]
+    fn insert_signed_and_float_values() {
+        let qstring = "INSERT INTO t VALUES (-1, 2.5, 3e2, +4, -0.25)";
+
+        let res = insertion(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.data,
+            InsertData::Values(vec![vec![
+                Literal::Integer(-1),
+                Literal::FixedPoint(Real {
+                    integral: 2,
+                    fractional: 5,
+                }),
+                Literal::Float(300.0),
+                Literal::Integer(4),
+                Literal::Float(-0.25),
+            ]])
+        );
+        assert_eq!(
+            format!("{}", res),
+            "INSERT INTO t VALUES (-1, 2.5, 3e2, 4, -0.25)"
+        );
+    }
+
+    #[test]
+    fn insert_default_values() {
+        let qstring = "INSERT INTO t (a, b, c) VALUES (DEFAULT, 5, default)";
+
+        let res = insertion(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.data,
+            InsertData::Values(vec![vec![
+                Literal::Default,
+                Literal::Integer(5),
+                Literal::Default,
+            ]])
+        );
+        assert_eq!(
+            format!("{}", res),
+            "INSERT INTO t (a, b, c) VALUES (DEFAULT, 5, DEFAULT)"
+        );
+
+        // not a keyword, and not a literal either
+        assert!(insertion(b"INSERT INTO t VALUES (defaults)").is_err());
+    }
+
     #[test]
     fn simple_insert_schema() {
         let qstring = "INSERT INTO db1.users VALUES (42, \"test\");";
@@ -147,7 +364,7 @@ mod tests {
             InsertStatement {
                 table: Table::from(("db1","users")),
                 fields: None,
-                data: vec![vec![42.into(), "test".into()]],
+                data: InsertData::Values(vec![vec![42.into(), "test".into()]]),
                 ..Default::default()
             }
         );
@@ -163,12 +380,12 @@ mod tests {
             InsertStatement {
                 table: Table::from("users"),
                 fields: None,
-                data: vec![vec![
+                data: InsertData::Values(vec![vec![
                     42.into(),
                     "test".into(),
                     "test".into(),
                     Literal::CurrentTimestamp,
-                ],],
+                ],]),
                 ..Default::default()
             }
         );
@@ -184,7 +401,7 @@ mod tests {
             InsertStatement {
                 table: Table::from("users"),
                 fields: Some(vec![Column::from("id"), Column::from("name")]),
-                data: vec![vec![42.into(), "test".into()]],
+                data: InsertData::Values(vec![vec![42.into(), "test".into()]]),
                 ..Default::default()
             }
         );
@@ -201,7 +418,7 @@ mod tests {
             InsertStatement {
                 table: Table::from("users"),
                 fields: Some(vec![Column::from("id"), Column::from("name")]),
-                data: vec![vec![42.into(), "test".into()]],
+                data: InsertData::Values(vec![vec![42.into(), "test".into()]]),
                 ..Default::default()
             }
         );
@@ -217,13 +434,36 @@ mod tests {
             InsertStatement {
                 table: Table::from("users"),
                 fields: Some(vec![Column::from("id"), Column::from("name")]),
-                data: vec![
+                data: InsertData::Values(vec![
+                    vec![42.into(), "test".into()],
+                    vec![21.into(), "test2".into()],
+                ]),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn insert_value_tuple_spacing() {
+        let qstring = "INSERT INTO users(id, name)VALUES ( 42, \"test\" ) , (21,\"test2\")";
+
+        let res = insertion(qstring.as_bytes());
+        assert_eq!(
+            res.unwrap().1,
+            InsertStatement {
+                table: Table::from("users"),
+                fields: Some(vec![Column::from("id"), Column::from("name")]),
+                data: InsertData::Values(vec![
                     vec![42.into(), "test".into()],
                     vec![21.into(), "test2".into()],
-                ],
+                ]),
                 ..Default::default()
             }
         );
+
+        // value tuples must be separated by commas
+        let qstring = "INSERT INTO users VALUES (42, \"test\") (21, \"test2\")";
+        assert!(insertion(qstring.as_bytes()).is_err());
     }
 
     #[test]
@@ -236,15 +476,29 @@ mod tests {
             InsertStatement {
                 table: Table::from("users"),
                 fields: Some(vec![Column::from("id"), Column::from("name")]),
-                data: vec![vec![
+                data: InsertData::Values(vec![vec![
                     Literal::Placeholder(ItemPlaceholder::QuestionMark),
                     Literal::Placeholder(ItemPlaceholder::QuestionMark)
-                ]],
+                ]]),
                 ..Default::default()
             }
         );
     }
 
+    #[test]
+    fn insert_with_named_parameters() {
+        let qstring = "INSERT INTO users (id, name) VALUES (:id, :name);";
+
+        let res = insertion(qstring.as_bytes());
+        assert_eq!(
+            res.unwrap().1.data,
+            InsertData::Values(vec![vec![
+                Literal::Placeholder(ItemPlaceholder::ColonName(String::from("id"))),
+                Literal::Placeholder(ItemPlaceholder::ColonName(String::from("name"))),
+            ]])
+        );
+    }
+
     #[test]
     fn insert_with_on_dup_update() {
         let qstring = "INSERT INTO keystores (`key`, `value`) VALUES ($1, :2) \
@@ -262,10 +516,10 @@ mod tests {
             InsertStatement {
                 table: Table::from("keystores"),
                 fields: Some(vec![Column::from("key"), Column::from("value")]),
-                data: vec![vec![
+                data: InsertData::Values(vec![vec![
                     Literal::Placeholder(ItemPlaceholder::DollarNumber(1)),
                     Literal::Placeholder(ItemPlaceholder::ColonNumber(2))
-                ]],
+                ]]),
                 on_duplicate: Some(vec![(
                     Column::from("value"),
                     FieldValueExpression::Arithmetic(expected_ae),
@@ -275,6 +529,164 @@ mod tests {
         );
     }
 
+    #[test]
+    fn insert_with_on_dup_update_values() {
+        let qstring = "INSERT INTO counters (id, hits) VALUES (1, 1) \
+                       ON DUPLICATE KEY UPDATE hits = VALUES(hits), updated = 5";
+
+        let res = insertion(qstring.as_bytes()).unwrap().1;
+        let values_hits = Column {
+            name: String::from("VALUES(hits)"),
+            alias: None,
+            table: None,
+            function: Some(Box::new(FunctionExpression::Generic(
+                String::from("VALUES"),
+                FunctionArguments::from(vec![FunctionArgument::Column(Column::from("hits"))]),
+            ))),
+        };
+        assert_eq!(
+            res.on_duplicate,
+            Some(vec![
+                (
+                    Column::from("hits"),
+                    FieldValueExpression::Column(values_hits)
+                ),
+                (
+                    Column::from("updated"),
+                    FieldValueExpression::Literal(LiteralExpression {
+                        value: 5.into(),
+                        alias: None,
+                    })
+                ),
+            ])
+        );
+        assert_eq!(
+            format!("{}", res),
+            "INSERT INTO counters (id, hits) VALUES (1, 1) \
+             ON DUPLICATE KEY UPDATE hits = VALUES(hits), updated = 5"
+        );
+    }
+
+    #[test]
+    fn insert_on_conflict_do_nothing() {
+        let qstring = "INSERT INTO users (id, name) VALUES (1, 'bob') ON CONFLICT (id) DO NOTHING";
+
+        let res = insertion(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.on_conflict,
+            Some(OnConflict {
+                target: Some(vec![Column::from("id")]),
+                action: ConflictAction::DoNothing,
+            })
+        );
+        assert_eq!(format!("{}", res), qstring);
+
+        let qstring = "INSERT INTO users VALUES (1, 'bob') ON CONFLICT DO NOTHING";
+        let res = insertion(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.on_conflict,
+            Some(OnConflict {
+                target: None,
+                action: ConflictAction::DoNothing,
+            })
+        );
+        assert_eq!(format!("{}", res), qstring);
+    }
+
+    #[test]
+    fn insert_on_conflict_do_update() {
+        let qstring = "INSERT INTO counters (id, hits) VALUES (1, 1) \
+                       ON CONFLICT (id) DO UPDATE SET hits = hits + 1, updated = 5";
+
+        let res = insertion(qstring.as_bytes()).unwrap().1;
+        let expected_ae = ArithmeticExpression::new(
+            ArithmeticOperator::Add,
+            ArithmeticBase::Column(Column::from("hits")),
+            ArithmeticBase::Scalar(1.into()),
+            None,
+        );
+        assert_eq!(
+            res.on_conflict,
+            Some(OnConflict {
+                target: Some(vec![Column::from("id")]),
+                action: ConflictAction::DoUpdate(vec![
+                    (
+                        Column::from("hits"),
+                        FieldValueExpression::Arithmetic(expected_ae)
+                    ),
+                    (
+                        Column::from("updated"),
+                        FieldValueExpression::Literal(LiteralExpression {
+                            value: 5.into(),
+                            alias: None,
+                        })
+                    ),
+                ]),
+            })
+        );
+        assert_eq!(res.on_duplicate, None);
+        assert_eq!(
+            format!("{}", res),
+            "INSERT INTO counters (id, hits) VALUES (1, 1) \
+             ON CONFLICT (id) DO UPDATE SET hits = hits + 1, updated = 5"
+        );
+    }
+
+    #[test]
+    fn insert_on_conflict_depends_on_dialect() {
+        use dialect::{with_dialect, Dialect};
+
+        let qstring = "INSERT INTO users (id) VALUES (1) ON CONFLICT (id) DO NOTHING";
+        assert!(with_dialect(Dialect::SQLite, || insertion(qstring.as_bytes())).is_ok());
+        assert!(with_dialect(Dialect::MySQL, || insertion(qstring.as_bytes())).is_err());
+    }
+
+    #[test]
+    fn insert_select() {
+        let qstring = "INSERT INTO archive SELECT * FROM log;";
+
+        let res = insertion(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res,
+            InsertStatement {
+                table: Table::from("archive"),
+                data: InsertData::Select(Box::new(SelectStatement {
+                    tables: vec![Table::from("log")],
+                    fields: vec![FieldDefinitionExpression::All],
+                    ..Default::default()
+                })),
+                ..Default::default()
+            }
+        );
+        assert_eq!(format!("{}", res), "INSERT INTO archive SELECT * FROM log");
+
+        let qstring = "INSERT INTO archive (a, b) SELECT x, y FROM log WHERE x > 1";
+        let res = insertion(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.fields, Some(vec![Column::from("a"), Column::from("b")]));
+        match res.data {
+            InsertData::Select(select) => assert!(select.where_clause.is_some()),
+            _ => panic!("expected INSERT ... SELECT"),
+        }
+    }
+
+    #[test]
+    fn replace_into() {
+        let qstring = "REPLACE INTO t (id) VALUES (1);";
+
+        let res = insertion(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res,
+            InsertStatement {
+                table: Table::from("t"),
+                fields: Some(vec![Column::from("id")]),
+                data: InsertData::Values(vec![vec![1.into()]]),
+                replace: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(format!("{}", res), "REPLACE INTO t (id) VALUES (1)");
+    }
+
     #[test]
     fn insert_with_leading_value_whitespace() {
         let qstring = "INSERT INTO users (id, name) VALUES ( 42, \"test\");";
@@ -285,7 +697,7 @@ mod tests {
             InsertStatement {
                 table: Table::from("users"),
                 fields: Some(vec![Column::from("id"), Column::from("name")]),
-                data: vec![vec![42.into(), "test".into()]],
+                data: InsertData::Values(vec![vec![42.into(), "test".into()]]),
                 ..Default::default()
             }
         );
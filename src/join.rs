@@ -5,7 +5,9 @@ use column::Column;
 use condition::ConditionExpression;
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
+use nom::character::complete::multispace1;
 use nom::combinator::map;
+use nom::sequence::terminated;
 use nom::IResult;
 use select::{JoinClause, SelectStatement};
 use table::Table;
@@ -45,6 +47,7 @@ pub enum JoinOperator {
     LeftJoin,
     LeftOuterJoin,
     RightJoin,
+    RightOuterJoin,
     InnerJoin,
     CrossJoin,
     StraightJoin,
@@ -57,6 +60,7 @@ impl fmt::Display for JoinOperator {
             JoinOperator::LeftJoin => write!(f, "LEFT JOIN")?,
             JoinOperator::LeftOuterJoin => write!(f, "LEFT OUTER JOIN")?,
             JoinOperator::RightJoin => write!(f, "RIGHT JOIN")?,
+            JoinOperator::RightOuterJoin => write!(f, "RIGHT OUTER JOIN")?,
             JoinOperator::InnerJoin => write!(f, "INNER JOIN")?,
             JoinOperator::CrossJoin => write!(f, "CROSS JOIN")?,
             JoinOperator::StraightJoin => write!(f, "STRAIGHT JOIN")?,
@@ -69,6 +73,8 @@ impl fmt::Display for JoinOperator {
 pub enum JoinConstraint {
     On(ConditionExpression),
     Using(Vec<Column>),
+    /// No constraint, as in `a CROSS JOIN b`.
+    Empty,
 }
 
 impl fmt::Display for JoinConstraint {
@@ -84,22 +90,41 @@ impl fmt::Display for JoinConstraint {
                     .collect::<Vec<_>>()
                     .join(", ")
             )?,
+            JoinConstraint::Empty => (),
         }
         Ok(())
     }
 }
 
-// Parse binary comparison operators
+// Parse a `<kind> [OUTER] JOIN` keyword sequence
+fn join_keywords<'a>(
+    kind: &'static str,
+    outer: bool,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], ()> {
+    move |i| {
+        let (i, _) = terminated(tag_no_case(kind), multispace1)(i)?;
+        let (i, _) = if outer {
+            terminated(tag_no_case("outer"), multispace1)(i)?
+        } else {
+            (i, &b""[..])
+        };
+        let (i, _) = tag_no_case("join")(i)?;
+        Ok((i, ()))
+    }
+}
+
+// Parse join operators
 pub fn join_operator(i: &[u8]) -> IResult<&[u8], JoinOperator> {
     alt((
         map(tag_no_case("join"), |_| JoinOperator::Join),
-        map(tag_no_case("left join"), |_| JoinOperator::LeftJoin),
-        map(tag_no_case("left outer join"), |_| {
-            JoinOperator::LeftOuterJoin
+        map(join_keywords("left", false), |_| JoinOperator::LeftJoin),
+        map(join_keywords("left", true), |_| JoinOperator::LeftOuterJoin),
+        map(join_keywords("right", false), |_| JoinOperator::RightJoin),
+        map(join_keywords("right", true), |_| {
+            JoinOperator::RightOuterJoin
         }),
-        map(tag_no_case("right join"), |_| JoinOperator::RightJoin),
-        map(tag_no_case("inner join"), |_| JoinOperator::InnerJoin),
-        map(tag_no_case("cross join"), |_| JoinOperator::CrossJoin),
+        map(join_keywords("inner", false), |_| JoinOperator::InnerJoin),
+        map(join_keywords("cross", false), |_| JoinOperator::CrossJoin),
         map(tag_no_case("straight_join"), |_| JoinOperator::StraightJoin),
     ))(i)
 }
@@ -141,4 +166,29 @@ mod tests {
         assert_eq!(q, expected_stmt);
         assert_eq!(qstring, format!("{}", q));
     }
+
+    #[test]
+    fn outer_and_cross_joins() {
+        let qstring = "SELECT * FROM a \
+                       LEFT  OUTER JOIN b ON a.id = b.a_id \
+                       RIGHT OUTER JOIN c USING (id) \
+                       CROSS JOIN d";
+
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        let operators: Vec<_> = res.join.iter().map(|j| j.operator.clone()).collect();
+        assert_eq!(
+            operators,
+            vec![
+                JoinOperator::LeftOuterJoin,
+                JoinOperator::RightOuterJoin,
+                JoinOperator::CrossJoin,
+            ]
+        );
+        assert_eq!(res.join[2].constraint, JoinConstraint::Empty);
+        assert_eq!(
+            format!("{}", res),
+            "SELECT * FROM a LEFT OUTER JOIN b ON a.id = b.a_id \
+             RIGHT OUTER JOIN c USING (id) CROSS JOIN d"
+        );
+    }
 }
@@ -4,6 +4,8 @@ use nom::bytes::complete::{tag, tag_no_case};
 use nom::combinator::peek;
 use nom::sequence::terminated;
 use nom::IResult;
+use scanner::Scanner;
+use std::ops::Range;
 
 // NOTE: Each keyword_$start_letter_to_$end_letter function uses `alt`,
 // which is implemented for tuples sizes up to 21. Because of this constraint
@@ -197,3 +199,54 @@ pub fn escape_if_keyword(s: &str) -> String {
         s.to_owned()
     }
 }
+
+/// Returns the distinct reserved keywords appearing in `input`, uppercased, in order of first
+/// appearance. Quoted strings and identifiers and comments are skipped.
+pub fn keywords_in(input: &str) -> Vec<String> {
+    let bytes = input.as_bytes();
+    let is_word_char = |c: u8| c.is_ascii_alphanumeric() || c == b'_' || c == b'@';
+
+    let mut words: Vec<Range<usize>> = Vec::new();
+    let mut scanner = Scanner::new();
+    while let Some(i) = scanner.next_code(bytes, true) {
+        if !is_word_char(bytes[i]) {
+            continue;
+        }
+        match words.last_mut() {
+            Some(word) if word.end == i => word.end = i + 1,
+            _ => words.push(i..i + 1),
+        }
+    }
+
+    let mut keywords: Vec<String> = Vec::new();
+    for word in words {
+        if let Ok((rest, _)) = sql_keyword(&bytes[word.clone()]) {
+            let keyword = input[word].to_ascii_uppercase();
+            if rest.is_empty() && !keywords.contains(&keyword) {
+                keywords.push(keyword);
+            }
+        }
+    }
+    keywords
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_in_create_table() {
+        let qstring = "CREATE TABLE `order` (id int NOT NULL, note text DEFAULT 'key', \
+                       PRIMARY KEY (id), KEY idx (note));";
+        assert_eq!(
+            keywords_in(qstring),
+            vec!["CREATE", "TABLE", "NOT", "NULL", "DEFAULT", "PRIMARY", "KEY"]
+        );
+    }
+
+    #[test]
+    fn keywords_in_skips_comments() {
+        let qstring = "-- don't select\nDELETE /* from where */ FROM t # order by\n";
+        assert_eq!(keywords_in(qstring), vec!["DELETE", "FROM"]);
+    }
+}
@@ -7,47 +7,76 @@ extern crate serde_derive;
 #[cfg(test)]
 #[macro_use]
 extern crate pretty_assertions;
+#[cfg(test)]
+extern crate proptest;
 
+pub use self::alter::{AlterOperation, AlterTableStatement};
 pub use self::arithmetic::{ArithmeticBase, ArithmeticExpression, ArithmeticOperator};
-pub use self::case::{CaseWhenExpression, ColumnOrLiteral};
+pub use self::case::{CaseExpression, ColumnOrLiteral};
+pub use self::cast::{CastExpression, CastTarget};
 pub use self::column::{
     Column, ColumnConstraint, ColumnSpecification, FunctionArgument, FunctionExpression,
+    IndexColumn,
 };
 pub use self::common::{
-    FieldDefinitionExpression, FieldValueExpression, ItemPlaceholder, Literal, LiteralExpression,
-    Operator, Real, SqlType, TableKey,
+    FieldDefinitionExpression, FieldValueExpression, ForeignKeyReference, IndexType,
+    ItemPlaceholder, Literal, LiteralExpression, Operator, Real, ReferentialAction, SqlType,
+    SqlTypeFamily, TableKey,
 };
 pub use self::compound_select::{CompoundSelectOperator, CompoundSelectStatement};
 pub use self::condition::{ConditionBase, ConditionExpression, ConditionTree};
-pub use self::create::{CreateTableStatement, CreateViewStatement, SelectSpecification};
+pub use self::create::{
+    CreateDatabaseStatement, CreateIndexStatement, CreateTableBuilder, CreateTableStatement,
+    CreateViewStatement, CycleError, SelectSpecification, ViewCheckOption,
+};
+pub use self::create_table_options::{RowFormat, TableOption};
 pub use self::delete::DeleteStatement;
-pub use self::insert::InsertStatement;
+pub use self::dialect::Dialect;
+pub use self::drop::{DropIndexStatement, DropTableStatement};
+pub use self::insert::{ConflictAction, InsertData, InsertStatement, OnConflict};
 pub use self::join::{JoinConstraint, JoinOperator, JoinRightSide};
+pub use self::keywords::keywords_in;
 pub use self::order::{OrderClause, OrderType};
 pub use self::parser::*;
-pub use self::select::{GroupByClause, JoinClause, LimitClause, SelectStatement};
+pub use self::select::{
+    GroupByClause, JoinClause, LimitClause, LockClause, LockMode, LockWait, SelectStatement,
+};
 pub use self::set::SetStatement;
+pub use self::span::Span;
 pub use self::table::Table;
 pub use self::update::UpdateStatement;
+pub use self::visitor::{
+    walk_column_constraint, walk_column_specification, walk_create_table,
+    walk_foreign_key_reference, walk_table_key, Visitor,
+};
+pub use self::warning::Warning;
 
 pub mod parser;
 
 #[macro_use]
 mod keywords;
+mod alter;
 mod arithmetic;
 mod case;
+mod cast;
 mod column;
 mod common;
 mod compound_select;
 mod condition;
+mod context;
 mod create;
 mod create_table_options;
 mod delete;
+mod dialect;
 mod drop;
 mod insert;
 mod join;
 mod order;
+mod scanner;
 mod select;
 mod set;
+mod span;
 mod table;
 mod update;
+mod visitor;
+mod warning;
@@ -4,7 +4,6 @@ use std::str;
 
 use column::Column;
 use common::{column_identifier_no_alias, ws_sep_comma};
-use keywords::escape_if_keyword;
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
 use nom::combinator::{map, opt};
@@ -40,7 +39,7 @@ impl fmt::Display for OrderClause {
             "{}",
             self.columns
                 .iter()
-                .map(|&(ref c, ref o)| format!("{} {}", escape_if_keyword(&c.name), o))
+                .map(|(c, o)| format!("{} {}", c, o))
                 .collect::<Vec<_>>()
                 .join(", ")
         )
@@ -69,9 +68,11 @@ fn order_expr(i: &[u8]) -> IResult<&[u8], (Column, OrderType)> {
 
 // Parse ORDER BY clause
 pub fn order_clause(i: &[u8]) -> IResult<&[u8], OrderClause> {
-    let (remaining_input, (_, _, _, columns)) = tuple((
+    let (remaining_input, (_, _, _, _, _, columns)) = tuple((
         multispace0,
-        tag_no_case("order by"),
+        tag_no_case("order"),
+        multispace1,
+        tag_no_case("by"),
         multispace1,
         many0(order_expr),
     ))(i)?;
@@ -110,4 +111,24 @@ mod tests {
         assert_eq!(res2.unwrap().1.order, Some(expected_ord2));
         assert_eq!(res3.unwrap().1.order, Some(expected_ord3));
     }
+
+    #[test]
+    fn order_clause_qualified_columns() {
+        let qstring = "select * from users u order  by u.name, u.age DESC, `key` asc";
+
+        let expected_ord = OrderClause {
+            columns: vec![
+                ("u.name".into(), OrderType::OrderAscending),
+                ("u.age".into(), OrderType::OrderDescending),
+                ("key".into(), OrderType::OrderAscending),
+            ],
+        };
+
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.order, Some(expected_ord));
+        assert_eq!(
+            format!("{}", res.order.unwrap()),
+            "ORDER BY u.name ASC, u.age DESC, `key` ASC"
+        );
+    }
 }
@@ -1,29 +1,46 @@
+use std::collections::VecDeque;
 use std::fmt;
+use std::io::{self, Read};
+use std::ops::Range;
 use std::str;
 
+use alter::{alter_table, alter_table_without_operations, AlterTableStatement};
+use column::{Column, ColumnSpecification};
+use common::field_list;
 use compound_select::{compound_selection, CompoundSelectStatement};
-use create::{creation, view_creation, CreateTableStatement, CreateViewStatement};
+use create::{
+    column_specification_lenient, creation, database_creation, index_creation, view_creation,
+    CreateDatabaseStatement, CreateIndexStatement, CreateTableStatement, CreateViewStatement,
+};
 use delete::{deletion, DeleteStatement};
-use drop::{drop_table, DropTableStatement};
+use dialect::{self, Dialect};
+use drop::{drop_index, drop_table, DropIndexStatement, DropTableStatement};
 use insert::{insertion, InsertStatement};
 use nom::branch::alt;
 use nom::combinator::map;
 use nom::IResult;
+use scanner::Scanner;
 use select::{selection, SelectStatement};
 use set::{set, SetStatement};
+use span;
 use update::{updating, UpdateStatement};
+use warning::{self, Warning};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum SqlQuery {
     CreateTable(CreateTableStatement),
     CreateView(CreateViewStatement),
+    CreateIndex(CreateIndexStatement),
+    CreateDatabase(CreateDatabaseStatement),
     Insert(InsertStatement),
     CompoundSelect(CompoundSelectStatement),
     Select(SelectStatement),
     Delete(DeleteStatement),
     DropTable(DropTableStatement),
+    DropIndex(DropIndexStatement),
     Update(UpdateStatement),
     Set(SetStatement),
+    AlterTable(AlterTableStatement),
 }
 
 impl fmt::Display for SqlQuery {
@@ -33,10 +50,14 @@ impl fmt::Display for SqlQuery {
             SqlQuery::Insert(ref insert) => write!(f, "{}", insert),
             SqlQuery::CreateTable(ref create) => write!(f, "{}", create),
             SqlQuery::CreateView(ref create) => write!(f, "{}", create),
+            SqlQuery::CreateIndex(ref create) => write!(f, "{}", create),
+            SqlQuery::CreateDatabase(ref create) => write!(f, "{}", create),
             SqlQuery::Delete(ref delete) => write!(f, "{}", delete),
             SqlQuery::DropTable(ref drop) => write!(f, "{}", drop),
+            SqlQuery::DropIndex(ref drop) => write!(f, "{}", drop),
             SqlQuery::Update(ref update) => write!(f, "{}", update),
             SqlQuery::Set(ref set) => write!(f, "{}", set),
+            SqlQuery::AlterTable(ref alter) => write!(f, "{}", alter),
             _ => unimplemented!(),
         }
     }
@@ -44,24 +65,48 @@ impl fmt::Display for SqlQuery {
 
 pub fn sql_query(i: &[u8]) -> IResult<&[u8], SqlQuery> {
     alt((
-        map(creation, |c| SqlQuery::CreateTable(c)),
+        map(warning::discard_on_failure(creation), |c| {
+            SqlQuery::CreateTable(c)
+        }),
         map(insertion, |i| SqlQuery::Insert(i)),
         map(compound_selection, |cs| SqlQuery::CompoundSelect(cs)),
         map(selection, |s| SqlQuery::Select(s)),
         map(deletion, |d| SqlQuery::Delete(d)),
         map(drop_table, |dt| SqlQuery::DropTable(dt)),
+        map(drop_index, SqlQuery::DropIndex),
         map(updating, |u| SqlQuery::Update(u)),
         map(set, |s| SqlQuery::Set(s)),
         map(view_creation, |vc| SqlQuery::CreateView(vc)),
+        map(
+            warning::discard_on_failure(index_creation),
+            SqlQuery::CreateIndex,
+        ),
+        map(database_creation, SqlQuery::CreateDatabase),
+        map(alter_table, SqlQuery::AlterTable),
     ))(i)
 }
 
+// Returns true if `i` contains nothing but whitespace and `-- `, `#` or `/* */` comments.
+fn is_blank(i: &[u8]) -> bool {
+    let mut scanner = Scanner::new();
+    while let Some(pos) = scanner.next_code(i, true) {
+        if !i[pos].is_ascii_whitespace() {
+            return false;
+        }
+    }
+    !scanner.is_open()
+}
+
 pub fn parse_query_bytes<T>(input: T) -> Result<SqlQuery, &'static str>
 where
     T: AsRef<[u8]>,
 {
     match sql_query(input.as_ref()) {
-        Ok((_, o)) => Ok(o),
+        Ok((rest, o)) if is_blank(rest) => Ok(o),
+        Ok(_) => Err("unexpected input after statement"),
+        Err(_) if alter_table_without_operations(input.as_ref()).is_ok() => {
+            Err("ALTER TABLE requires at least one operation")
+        }
         Err(_) => Err("failed to parse query"),
     }
 }
@@ -73,12 +118,306 @@ where
     parse_query_bytes(input.as_ref().trim().as_bytes())
 }
 
+/// Like `parse_query`, but only parses a leading statement, and returns it along with the input
+/// that follows it (after its terminator, if any) instead of requiring the whole input to be one
+/// statement.
+pub fn parse_query_partial(input: &str) -> Result<(SqlQuery, &str), &'static str> {
+    let input = input.trim_start();
+    match sql_query(input.as_bytes()) {
+        Ok((rest, o)) => match input.get(input.len() - rest.len()..) {
+            Some(rest) => Ok((o, rest)),
+            None => Err("statement ends inside a character"),
+        },
+        Err(_) => Err("failed to parse query"),
+    }
+}
+
+/// Like `parse_query`, but follows the rules of `dialect` where dialects disagree.
+pub fn parse_query_with<T>(dialect: Dialect, input: T) -> Result<SqlQuery, &'static str>
+where
+    T: AsRef<str>,
+{
+    dialect::with_dialect(dialect, || parse_query(input))
+}
+
+/// Like `parse_query`, but also returns a warning for each construct that was recognized but is
+/// not represented in the parsed query, such as table options.
+pub fn parse_query_with_warnings<T>(input: T) -> Result<(SqlQuery, Vec<Warning>), &'static str>
+where
+    T: AsRef<str>,
+{
+    let input = input.as_ref();
+    match warning::collect_warnings(input.as_bytes(), || parse_query(input)) {
+        (Ok(query), warnings) => Ok((query, warnings)),
+        (Err(e), _) => Err(e),
+    }
+}
+
+/// Like `parse_query`, but also records where statements are in `input`, in their `span` field.
+/// Only `CREATE TABLE` statements record a span so far.
+pub fn parse_query_with_spans<T>(input: T) -> Result<SqlQuery, &'static str>
+where
+    T: AsRef<str>,
+{
+    let input = input.as_ref();
+    span::with_spans(input.as_bytes(), || parse_query(input))
+}
+
+/// Parses a standalone comma-separated list of (optionally table-qualified) columns, such as
+/// `a, b, t.c`. The whole input must be consumed.
+pub fn parse_column_list<T>(input: T) -> Result<Vec<Column>, &'static str>
+where
+    T: AsRef<str>,
+{
+    match field_list(input.as_ref().trim().as_bytes()) {
+        Ok((rest, columns)) if rest.is_empty() && !columns.is_empty() => Ok(columns),
+        _ => Err("failed to parse column list"),
+    }
+}
+
+/// Parses a single column definition such as `id bigint(20) NOT NULL`, tolerating constraints
+/// written before the type (`id NOT NULL bigint(20)`). The whole input must be consumed. Also
+/// returns a warning for each guess made, such as taking a column without a type to be TEXT.
+pub fn parse_column_specification_lenient<T>(
+    input: T,
+) -> Result<(ColumnSpecification, Vec<Warning>), &'static str>
+where
+    T: AsRef<str>,
+{
+    let input = input.as_ref().trim().as_bytes();
+    match warning::collect_warnings(input, || column_specification_lenient(input)) {
+        (Ok((&[], spec)), warnings) => Ok((spec, warnings)),
+        _ => Err("failed to parse column specification"),
+    }
+}
+
+// Keywords that may begin a statement understood by `sql_query`; used by the lenient splitter to
+// decide whether a blank line separates two statements.
+const STATEMENT_STARTS: [&str; 9] = [
+    "alter", "create", "delete", "drop", "insert", "replace", "select", "set", "update",
+];
+
+fn starts_statement(i: &str) -> bool {
+    let i = i.trim_start();
+    STATEMENT_STARTS.iter().any(|kw| {
+        i.len() > kw.len()
+            && i.is_char_boundary(kw.len())
+            && i[..kw.len()].eq_ignore_ascii_case(kw)
+            && i[kw.len()..].starts_with(|c: char| c.is_whitespace())
+    })
+}
+
+// Returns true if `i` (the input following a newline) begins with a whitespace-only line.
+fn starts_with_blank_line(i: &str) -> bool {
+    match i.find('\n') {
+        Some(end) => i[..end].trim().is_empty(),
+        None => false,
+    }
+}
+
+// Splits `input` at every `;` that is not inside a quoted string or a comment. In lenient mode, a
+// blank line followed by a recognizable statement start is treated as a boundary too. Comments
+// preceding a statement are dropped, leaving blank statements where there is nothing else.
+fn split_statements(input: &str, lenient: bool) -> Vec<&str> {
+    let bytes = input.as_bytes();
+    let mut statements = Vec::new();
+    // the first byte of code in the current statement, and the end of the previous one
+    let mut start = None;
+    let mut end = 0;
+    let mut scanner = Scanner::new();
+    while let Some(i) = scanner.next_code(bytes, true) {
+        match bytes[i] {
+            b';' => {
+                statements.push(&input[start.take().unwrap_or(i)..i]);
+                end = i + 1;
+            }
+            b'\n'
+                if lenient
+                    && start.is_some()
+                    && starts_with_blank_line(&input[i + 1..])
+                    && starts_statement(&input[i + 1..]) =>
+            {
+                statements.extend(start.take().map(|start| &input[start..i]));
+                end = i + 1;
+            }
+            c if start.is_none() && !c.is_ascii_whitespace() => start = Some(i),
+            _ => (),
+        }
+    }
+    statements.push(match start {
+        Some(start) => &input[start..],
+        // an unterminated comment
+        None if scanner.is_open() => &input[end..],
+        None => "",
+    });
+    statements
+}
+
+// Parses each statement in turn, skipping blank ones such as those left by `;;`.
+fn parse_split_statements(statements: Vec<&str>) -> Result<Vec<SqlQuery>, &'static str> {
+    statements
+        .into_iter()
+        .filter(|s| !s.trim().is_empty())
+        .map(parse_query)
+        .collect()
+}
+
+/// Parses a sequence of `;`-separated statements. Empty statements are skipped.
+pub fn parse_statements<T>(input: T) -> Result<Vec<SqlQuery>, &'static str>
+where
+    T: AsRef<str>,
+{
+    parse_split_statements(split_statements(input.as_ref(), false))
+}
+
+/// Like `parse_statements`, but also treats a blank line followed by the start of a new statement
+/// as a statement boundary, for tools that omit semicolons. This is a heuristic and so is opt-in.
+pub fn parse_statements_lenient<T>(input: T) -> Result<Vec<SqlQuery>, &'static str>
+where
+    T: AsRef<str>,
+{
+    parse_split_statements(split_statements(input.as_ref(), true))
+}
+
+/// Like `parse_statements`, but a statement that fails to parse does not abort the whole batch:
+/// the parser resyncs at the next `;` and continues. Returns the statements that parsed, along
+/// with the byte range in `input` of each statement that was skipped.
+pub fn parse_statements_recovering<T>(input: T) -> (Vec<SqlQuery>, Vec<Range<usize>>)
+where
+    T: AsRef<str>,
+{
+    let input = input.as_ref();
+    let mut statements = Vec::new();
+    let mut skipped = Vec::new();
+    for statement in split_statements(input, false) {
+        let trimmed = statement.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        match parse_query(trimmed) {
+            Ok(query) => statements.push(query),
+            Err(_) => {
+                let start = trimmed.as_ptr() as usize - input.as_ptr() as usize;
+                skipped.push(start..start + trimmed.len());
+            }
+        }
+    }
+    (statements, skipped)
+}
+
+/// Incrementally splits `;`-separated statements out of input that arrives in chunks, such as a
+/// large dump read from disk, and parses each one as soon as it is complete. Chunks may split a
+/// statement (or a multi-byte character) anywhere. Comments preceding a statement are dropped,
+/// and blank statements are skipped.
+#[derive(Debug, Default)]
+pub struct StatementStream {
+    buf: Vec<u8>,
+    scanner: Scanner,
+    // Offset in `buf` of the first byte of code in the statement being read, once there is one.
+    start: Option<usize>,
+}
+
+impl StatementStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `chunk` to the input and returns every statement it completes, in order.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Result<SqlQuery, &'static str>> {
+        self.buf.extend_from_slice(chunk);
+
+        let mut statements = Vec::new();
+        let mut end = 0;
+        while let Some(i) = self.scanner.next_code(&self.buf, false) {
+            match self.buf[i] {
+                b';' => {
+                    let start = self.start.take().unwrap_or(i);
+                    statements.extend(parse_stream_statement(&self.buf[start..i]));
+                    end = i + 1;
+                }
+                c if self.start.is_none() && !c.is_ascii_whitespace() => self.start = Some(i),
+                _ => (),
+            }
+        }
+        self.buf.drain(..end);
+        self.scanner.discard(end);
+        self.start = self.start.map(|start| start - end);
+        statements
+    }
+
+    /// Parses whatever follows the last `;`, if it is not blank.
+    pub fn finish(mut self) -> Option<Result<SqlQuery, &'static str>> {
+        while let Some(i) = self.scanner.next_code(&self.buf, true) {
+            if self.start.is_none() && !self.buf[i].is_ascii_whitespace() {
+                self.start = Some(i);
+            }
+        }
+        match self.start {
+            Some(start) => parse_stream_statement(&self.buf[start..]),
+            // an unterminated comment
+            None if self.scanner.is_open() => Some(parse_query_bytes(&self.buf)),
+            None => None,
+        }
+    }
+}
+
+fn parse_stream_statement(statement: &[u8]) -> Option<Result<SqlQuery, &'static str>> {
+    match str::from_utf8(statement) {
+        Ok(s) if s.trim().is_empty() => None,
+        Ok(s) => Some(parse_query(s)),
+        Err(_) => Some(Err("statement is not valid UTF-8")),
+    }
+}
+
+/// An iterator over the statements read from an `io::Read`; see `parse_statements_from_reader`.
+pub struct StatementReader<R> {
+    reader: R,
+    chunk: Vec<u8>,
+    stream: Option<StatementStream>,
+    pending: VecDeque<Result<SqlQuery, &'static str>>,
+}
+
+impl<R: Read> Iterator for StatementReader<R> {
+    type Item = Result<SqlQuery, &'static str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            let stream = self.stream.as_mut()?;
+            match self.reader.read(&mut self.chunk) {
+                Ok(0) => {
+                    let last = self.stream.take().and_then(StatementStream::finish);
+                    self.pending.extend(last);
+                }
+                Ok(n) => self.pending.extend(stream.feed(&self.chunk[..n])),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(_) => {
+                    self.stream = None;
+                    self.pending.push_back(Err("failed to read input"));
+                }
+            }
+        }
+        self.pending.pop_front()
+    }
+}
+
+/// Like `parse_statements`, but reads the input incrementally from `reader` rather than requiring
+/// it all in memory, yielding each statement as soon as its terminating `;` has been read.
+pub fn parse_statements_from_reader<R: Read>(reader: R) -> StatementReader<R> {
+    StatementReader {
+        reader,
+        chunk: vec![0; 64 * 1024],
+        stream: Some(StatementStream::new()),
+        pending: VecDeque::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
 
+    use insert::InsertData;
     use table::Table;
 
     #[test]
@@ -90,7 +429,7 @@ mod tests {
         let expected = SqlQuery::Insert(InsertStatement {
             table: Table::from("users"),
             fields: None,
-            data: vec![vec![42.into(), "test".into()]],
+            data: InsertData::Values(vec![vec![42.into(), "test".into()]]),
             ..Default::default()
         });
         let mut h0 = DefaultHasher::new();
@@ -269,4 +608,347 @@ mod tests {
         assert_eq!(expected0, format!("{}", res0.unwrap()));
         assert_eq!(expected1, format!("{}", res1.unwrap()));
     }
+
+    #[test]
+    fn parse_multiple_statements() {
+        let qstring = "SELECT * FROM users; DELETE FROM users WHERE name = 'a;b';\n";
+        let res = parse_statements(qstring).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(format!("{}", res[0]), "SELECT * FROM users");
+        assert_eq!(
+            format!("{}", res[1]),
+            "DELETE FROM users WHERE name = 'a;b'"
+        );
+    }
+
+    #[test]
+    fn parse_statements_skips_empty_statements() {
+        let qstring = ";\n\nSELECT * FROM users;;\n  ;\nDELETE FROM users;\n\n;;\n";
+        let res = parse_statements(qstring).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(format!("{}", res[0]), "SELECT * FROM users");
+        assert_eq!(format!("{}", res[1]), "DELETE FROM users");
+
+        assert_eq!(parse_statements(";;").unwrap().len(), 0);
+        assert_eq!(parse_statements_lenient("\n;\n").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn parse_statements_separated_by_blank_line() {
+        let qstring = "SELECT id\nFROM users\n\nDELETE FROM users\nWHERE id = 1\n";
+        assert!(parse_statements(qstring).is_err());
+
+        let res = parse_statements_lenient(qstring).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(format!("{}", res[0]), "SELECT id FROM users");
+        assert_eq!(format!("{}", res[1]), "DELETE FROM users WHERE id = 1");
+
+        // blank lines inside a string literal are not boundaries
+        let qstring = "INSERT INTO t VALUES ('a\n\nselect b')";
+        assert_eq!(parse_statements_lenient(qstring).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn parse_alter_table_separated_by_blank_line() {
+        let qstring = "SELECT a FROM t\n\nALTER TABLE t ADD COLUMN b int";
+        let res = parse_statements_lenient(qstring).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(format!("{}", res[1]), "ALTER TABLE t ADD COLUMN b INT");
+    }
+
+    #[test]
+    fn parse_replace_separated_by_blank_line() {
+        let qstring = "SELECT a FROM t\n\nREPLACE INTO t VALUES (1)";
+        let res = parse_statements_lenient(qstring).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(format!("{}", res[1]), "REPLACE INTO t VALUES (1)");
+    }
+
+    #[test]
+    fn parse_statements_ignores_quotes_in_comments() {
+        let qstring = "-- the users' table\nSELECT * FROM users;\n\
+                       /* don't; stop */ DELETE FROM users WHERE name = 'a;b';\n\
+                       # that's all\n";
+        let res = parse_statements(qstring).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(format!("{}", res[0]), "SELECT * FROM users");
+        assert_eq!(
+            format!("{}", res[1]),
+            "DELETE FROM users WHERE name = 'a;b'"
+        );
+
+        let (statements, skipped) = parse_statements_recovering(qstring);
+        assert_eq!((statements.len(), skipped.len()), (2, 0));
+        assert!(parse_statements("SELECT * FROM users; /* it's").is_err());
+    }
+
+    #[test]
+    fn parse_statements_recovering_skips_broken_statement() {
+        let qstring = "SELECT FROM WHERE;\nSELECT id FROM users;";
+        assert!(parse_statements(qstring).is_err());
+
+        let (statements, skipped) = parse_statements_recovering(qstring);
+        assert_eq!(statements.len(), 1);
+        assert_eq!(format!("{}", statements[0]), "SELECT id FROM users");
+        assert_eq!(skipped, vec![0..17]);
+        assert_eq!(&qstring[skipped[0].clone()], "SELECT FROM WHERE");
+    }
+
+    #[test]
+    fn statement_stream_reassembles_chunks() {
+        let input = "INSERT INTO t (a) VALUES ('x;y', \"it\\\"s;\");\n\
+                     SELECT * FROM t WHERE name = 'ünïcode';\n\
+                     DELETE FROM t";
+        let expected: Vec<_> = parse_statements(input)
+            .unwrap()
+            .into_iter()
+            .map(Ok)
+            .collect();
+        assert_eq!(expected.len(), 3);
+
+        for size in 1..8 {
+            let mut stream = StatementStream::new();
+            let mut statements = Vec::new();
+            for chunk in input.as_bytes().chunks(size) {
+                statements.extend(stream.feed(chunk));
+            }
+            assert_eq!(statements.len(), 2, "chunk size {}", size);
+            statements.extend(stream.finish());
+            assert_eq!(statements, expected, "chunk size {}", size);
+        }
+    }
+
+    #[test]
+    fn statement_stream_skips_comments() {
+        let input = "-- don't split here;\nSELECT * FROM t;\n\
+                     /* it's; a comment */ DELETE FROM t;\n# trailing ' comment\n";
+        for size in 1..8 {
+            let mut stream = StatementStream::new();
+            let mut statements = Vec::new();
+            for chunk in input.as_bytes().chunks(size) {
+                statements.extend(stream.feed(chunk));
+            }
+            assert_eq!(stream.finish(), None, "chunk size {}", size);
+            let statements: Vec<_> = statements
+                .into_iter()
+                .map(|s| format!("{}", s.unwrap()))
+                .collect();
+            assert_eq!(statements, ["SELECT * FROM t", "DELETE FROM t"]);
+        }
+
+        let mut stream = StatementStream::new();
+        assert!(stream.feed(b"SELECT * FROM t; /* unterminated;").len() == 1);
+        assert!(stream.finish().unwrap().is_err());
+    }
+
+    #[test]
+    fn parse_statements_from_reader_reads_in_chunks() {
+        // yields at most three bytes per read, to split statements mid-way
+        struct Trickle<'a>(&'a [u8]);
+        impl<'a> Read for Trickle<'a> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let n = self.0.len().min(buf.len()).min(3);
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+
+        let input = "SELECT id FROM users;;\nSELECT FROM WHERE;\nDELETE FROM users WHERE id = 1;\n";
+        let statements: Vec<_> = parse_statements_from_reader(Trickle(input.as_bytes())).collect();
+        assert_eq!(statements.len(), 3);
+        assert_eq!(
+            format!("{}", statements[0].as_ref().unwrap()),
+            "SELECT id FROM users"
+        );
+        assert!(statements[1].is_err());
+        assert_eq!(
+            format!("{}", statements[2].as_ref().unwrap()),
+            "DELETE FROM users WHERE id = 1"
+        );
+    }
+
+    #[test]
+    fn alter_table_without_operations_error() {
+        assert!(parse_query("ALTER TABLE users ADD age int").is_ok());
+        assert_eq!(
+            parse_query("ALTER TABLE users;"),
+            Err("ALTER TABLE requires at least one operation")
+        );
+    }
+
+    #[test]
+    fn column_list() {
+        assert_eq!(
+            parse_column_list("a, b, c"),
+            Ok(vec![
+                Column::from("a"),
+                Column::from("b"),
+                Column::from("c")
+            ])
+        );
+        assert_eq!(
+            parse_column_list(" users.id,`name` "),
+            Ok(vec![Column::from("users.id"), Column::from("name")])
+        );
+        assert!(parse_column_list("a, b +").is_err());
+        assert!(parse_column_list("").is_err());
+    }
+
+    #[test]
+    fn double_quotes_depend_on_dialect() {
+        use common::{FieldDefinitionExpression, FieldValueExpression, Literal};
+
+        let qstring = "SELECT \"name\" FROM users";
+        let fields = |query| match query {
+            Ok(SqlQuery::Select(select)) => select.fields,
+            other => panic!("unexpected {:?}", other),
+        };
+
+        let string = FieldDefinitionExpression::Value(FieldValueExpression::Literal(
+            Literal::String(String::from("name")).into(),
+        ));
+        assert_eq!(
+            fields(parse_query_with(Dialect::MySQL, qstring)),
+            vec![string.clone()]
+        );
+        assert_eq!(
+            fields(parse_query_with(Dialect::PostgreSQL, qstring)),
+            vec![FieldDefinitionExpression::Col(Column::from("name"))]
+        );
+        assert_eq!(
+            fields(parse_query_with(Dialect::Ansi, qstring)),
+            vec![FieldDefinitionExpression::Col(Column::from("name"))]
+        );
+        // the default mode is unaffected by earlier dialect-specific parsing
+        assert_eq!(fields(parse_query(qstring)), vec![string]);
+
+        let qstring = "SELECT * FROM users WHERE name = \"x\"";
+        assert_eq!(
+            format!("{}", parse_query_with(Dialect::MySQL, qstring).unwrap()),
+            "SELECT * FROM users WHERE name = 'x'"
+        );
+        for &dialect in &[Dialect::Ansi, Dialect::PostgreSQL, Dialect::SQLite] {
+            assert_eq!(
+                format!("{}", parse_query_with(dialect, qstring).unwrap()),
+                "SELECT * FROM users WHERE name = x"
+            );
+        }
+        assert!(parse_query_with(Dialect::Ansi, "SELECT `name` FROM users").is_err());
+
+        assert!(parse_query_with(Dialect::SQLite, "SELECT `name` FROM [users]").is_ok());
+        assert!(parse_query_with(Dialect::PostgreSQL, "SELECT `name` FROM users").is_err());
+    }
+
+    #[test]
+    fn dialect_specific_types() {
+        let qstring = "CREATE TABLE t (id SERIAL, flag BOOLEAN)";
+        let format = |dialect| format!("{}", parse_query_with(dialect, qstring).unwrap());
+
+        assert_eq!(
+            format(Dialect::MySQL),
+            "CREATE TABLE t (\
+             id BIGINT(20) UNSIGNED NOT NULL AUTO_INCREMENT UNIQUE, flag TINYINT(1))"
+        );
+        assert_eq!(
+            format(Dialect::PostgreSQL),
+            "CREATE TABLE t (id INT NOT NULL AUTO_INCREMENT, flag BOOL)"
+        );
+        assert!(parse_query_with(Dialect::SQLite, qstring).is_err());
+    }
+
+    #[test]
+    fn on_duplicate_key_is_mysql_only() {
+        let qstring = "INSERT INTO t (id, n) VALUES (1, 1) ON DUPLICATE KEY UPDATE n = n + 1";
+        assert!(parse_query(qstring).is_ok());
+        assert!(parse_query_with(Dialect::MySQL, qstring).is_ok());
+        assert!(parse_query_with(Dialect::PostgreSQL, qstring).is_err());
+        assert!(parse_query_with(Dialect::SQLite, qstring).is_err());
+    }
+
+    #[test]
+    fn trailing_input() {
+        let qstring = "SELECT * FROM users; SELECT 1";
+        assert_eq!(
+            parse_query(qstring),
+            Err("unexpected input after statement")
+        );
+
+        let (query, rest) = parse_query_partial(qstring).unwrap();
+        assert_eq!(query, parse_query("SELECT * FROM users;").unwrap());
+        assert_eq!(rest, "SELECT 1");
+
+        let (_, rest) = parse_query_partial("  INSERT INTO t VALUES (1);  ").unwrap();
+        assert_eq!(rest, "");
+        assert!(parse_query_partial("garbage").is_err());
+    }
+
+    #[test]
+    fn trailing_garbage_is_an_error() {
+        assert_eq!(
+            parse_query("CREATE TABLE t (id int); garbage"),
+            Err("unexpected input after statement")
+        );
+        assert!(parse_query("SELECT * FROM t; /* unterminated").is_err());
+        assert!(parse_query("SELECT * FROM t; --x").is_err());
+
+        let qstring = "CREATE TABLE t (id int); -- the table\n# more\n/* and */ /**/ \n";
+        assert!(parse_query(qstring).is_ok());
+        assert!(parse_query("SELECT * FROM t; --").is_ok());
+    }
+
+    #[test]
+    fn invalid_utf8_is_an_error() {
+        for qstring in &[
+            &b"SELECT \xff FROM t"[..],
+            &b"CREATE TABLE t (\xff int)"[..],
+            &b"SELECT a FROM \xfe\xff"[..],
+            &b"SELECT * FROM t WHERE a = :\xff"[..],
+            &b"CREATE TABLE t (a int DEFAULT (\xff))"[..],
+            &b"CREATE TABLE t (a int AS (\xff))"[..],
+            &b"CREATE TABLE t (a int) PARTITION BY \xff"[..],
+        ] {
+            assert_eq!(parse_query_bytes(qstring), Err("failed to parse query"));
+        }
+        assert!(parse_query_bytes("SELECT caf\u{e9} FROM t".as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn warnings_for_discarded_clauses() {
+        let qstring = "  CREATE TABLE t (id int, name varchar(10), PRIMARY KEY (id), \
+                       KEY name_idx (name(4))) ENGINE=InnoDB";
+        let (query, warnings) = parse_query_with_warnings(qstring).unwrap();
+        assert_eq!(query, parse_query(qstring).unwrap());
+
+        // the index column's prefix length is represented, the table option is not
+        let offsets: Vec<_> = warnings.iter().map(|w| w.offset).collect();
+        assert_eq!(offsets, vec![qstring.find("ENGINE").unwrap()]);
+        assert_eq!(
+            warnings[0].message,
+            "table option `ENGINE=InnoDB` is not represented"
+        );
+
+        let (_, warnings) = parse_query_with_warnings("SELECT * FROM t").unwrap();
+        assert!(warnings.is_empty());
+        assert!(parse_query_with_warnings("CREATE TABLE t (id int) ENGINE=InnoDB x").is_err());
+    }
+
+    #[test]
+    fn spans_cover_create_table_statements() {
+        let statement = "CREATE TABLE t (id int, name varchar(10)) ENGINE=InnoDB";
+        let qstring = format!("  \n{} ;  ", statement);
+        let query = parse_query_with_spans(&qstring).unwrap();
+        let span = match query {
+            SqlQuery::CreateTable(ref create) => create.span.unwrap(),
+            _ => panic!("expected a CREATE TABLE statement"),
+        };
+        assert_eq!(&qstring[Range::from(span)], statement);
+        assert_eq!(span.start, 3);
+
+        // spans are opt-in
+        match parse_query(&qstring).unwrap() {
+            SqlQuery::CreateTable(create) => assert_eq!(create.span, None),
+            _ => panic!("expected a CREATE TABLE statement"),
+        }
+    }
 }
@@ -0,0 +1,178 @@
+// Where the scanner is in the input: in code, or inside a quoted string or identifier (possibly
+// right after a backslash) or a comment. A block comment remembers whether it just saw a `*`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+    Code,
+    Quoted(u8),
+    Escaped(u8),
+    LineComment,
+    BlockComment { star: bool },
+}
+
+/// Finds the bytes of SQL text that are code, i.e. outside quoted strings and identifiers and
+/// outside `-- `, `#` and `/* */` comments, so that statement separators and keywords can be
+/// found without a full parse. The quotes themselves and the newline ending a line comment count
+/// as code.
+///
+/// The input may arrive in pieces: the scanner resumes where it stopped, and when told the input
+/// is incomplete it waits for more before deciding whether a `-` or `/` starts a comment.
+#[derive(Clone, Debug)]
+pub(crate) struct Scanner {
+    state: State,
+    pos: usize,
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Scanner {
+            state: State::Code,
+            pos: 0,
+        }
+    }
+}
+
+impl Scanner {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the position in `input` of the next code byte, continuing from the previous call.
+    /// Returns `None` once `input` is exhausted, or, unless `complete`, when the next byte cannot
+    /// be classified without seeing more input.
+    pub(crate) fn next_code(&mut self, input: &[u8], complete: bool) -> Option<usize> {
+        while let Some(&c) = input.get(self.pos) {
+            let i = self.pos;
+            match self.state {
+                State::Code => {
+                    let comment = match c {
+                        b'-' | b'/' if !complete && input.len() - i < 3 => {
+                            match (c, input.get(i + 1)) {
+                                (_, None) | (b'-', Some(&b'-')) => return None,
+                                _ => (),
+                            }
+                            starts_comment(&input[i..])
+                        }
+                        b'-' | b'/' | b'#' => starts_comment(&input[i..]),
+                        _ => None,
+                    };
+                    match comment {
+                        Some(state) => {
+                            self.state = state;
+                            self.pos += if c == b'#' { 1 } else { 2 };
+                        }
+                        None => {
+                            if c == b'\'' || c == b'"' || c == b'`' {
+                                self.state = State::Quoted(c);
+                            }
+                            self.pos += 1;
+                            return Some(i);
+                        }
+                    }
+                    continue;
+                }
+                State::Quoted(q) if c == b'\\' && q != b'`' => self.state = State::Escaped(q),
+                State::Quoted(q) if c == q => {
+                    self.state = State::Code;
+                    self.pos += 1;
+                    return Some(i);
+                }
+                State::Quoted(_) => (),
+                State::Escaped(q) => self.state = State::Quoted(q),
+                State::LineComment if c == b'\n' => {
+                    // leave the newline to be returned as code
+                    self.state = State::Code;
+                    continue;
+                }
+                State::LineComment => (),
+                State::BlockComment { star } => {
+                    self.state = if star && c == b'/' {
+                        State::Code
+                    } else {
+                        State::BlockComment { star: c == b'*' }
+                    };
+                }
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    /// Returns true if the input scanned so far ends inside a quote or a block comment.
+    pub(crate) fn is_open(&self) -> bool {
+        match self.state {
+            State::Code | State::LineComment => false,
+            State::Quoted(_) | State::Escaped(_) | State::BlockComment { .. } => true,
+        }
+    }
+
+    /// Accounts for the first `n` bytes of the input, all of them already scanned, having been
+    /// removed.
+    pub(crate) fn discard(&mut self, n: usize) {
+        self.pos -= n;
+    }
+}
+
+// Returns the state entered if `i` begins with a comment: `#`, `--` followed by whitespace or the
+// end of input, or `/*`.
+fn starts_comment(i: &[u8]) -> Option<State> {
+    if i.starts_with(b"#") || (i.starts_with(b"--") && i.get(2).is_none_or(u8::is_ascii_whitespace))
+    {
+        Some(State::LineComment)
+    } else if i.starts_with(b"/*") {
+        Some(State::BlockComment { star: false })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(input: &str) -> String {
+        let mut scanner = Scanner::new();
+        let mut code = String::new();
+        while let Some(i) = scanner.next_code(input.as_bytes(), true) {
+            code.push(input.as_bytes()[i] as char);
+        }
+        code
+    }
+
+    #[test]
+    fn skips_quotes_and_comments() {
+        assert_eq!(code("a 'b;c' \"d\" `e`;"), "a '' \"\" ``;");
+        assert_eq!(code("a 'it''s' 'x\\'y' b"), "a '''' '' b");
+        assert_eq!(code("a -- Host's data\nb # c'\nd"), "a \nb \nd");
+        assert_eq!(code("a /* it's; */ b /**/ c"), "a  b  c");
+        assert_eq!(code("a--b - -c a/b --"), "a--b - -c a/b ");
+    }
+
+    #[test]
+    fn resumes_across_chunks() {
+        let input = b"a -- x\nb /* y */ c";
+        for split in 0..input.len() {
+            let mut buf = input[..split].to_vec();
+            let mut scanner = Scanner::new();
+            let mut code = vec![];
+            while let Some(i) = scanner.next_code(&buf, false) {
+                code.push(buf[i]);
+            }
+            buf.extend_from_slice(&input[split..]);
+            while let Some(i) = scanner.next_code(&buf, true) {
+                code.push(buf[i]);
+            }
+            assert_eq!(code, b"a \nb  c", "split at {}", split);
+        }
+    }
+
+    #[test]
+    fn open_quotes_and_comments() {
+        let mut scanner = Scanner::new();
+        while scanner.next_code(b"a /* b", true).is_some() {}
+        assert!(scanner.is_open());
+
+        let mut scanner = Scanner::new();
+        while scanner.next_code(b"a -- b", true).is_some() {}
+        assert!(!scanner.is_open());
+    }
+}
@@ -6,7 +6,7 @@ use column::Column;
 use common::FieldDefinitionExpression;
 use common::{
     as_alias, field_definition_expr, field_list, statement_terminator, table_list, table_reference,
-    unsigned_number,
+    unsigned_number, ws_sep_comma,
 };
 use condition::{condition_expr, ConditionExpression};
 use join::{join_operator, JoinConstraint, JoinOperator, JoinRightSide};
@@ -14,7 +14,7 @@ use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
 use nom::combinator::{map, opt};
 use nom::multi::many0;
-use nom::sequence::{delimited, preceded, terminated, tuple};
+use nom::sequence::{delimited, preceded, separated_pair, terminated, tuple};
 use nom::IResult;
 use order::{order_clause, OrderClause};
 use table::Table;
@@ -23,6 +23,7 @@ use table::Table;
 pub struct GroupByClause {
     pub columns: Vec<Column>,
     pub having: Option<ConditionExpression>,
+    pub with_rollup: bool,
 }
 
 impl fmt::Display for GroupByClause {
@@ -37,6 +38,9 @@ impl fmt::Display for GroupByClause {
                 .collect::<Vec<_>>()
                 .join(", ")
         )?;
+        if self.with_rollup {
+            write!(f, " WITH ROLLUP")?;
+        }
         if let Some(ref having) = self.having {
             write!(f, " HAVING {}", having)?;
         }
@@ -55,7 +59,9 @@ impl fmt::Display for JoinClause {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.operator)?;
         write!(f, " {}", self.right)?;
-        write!(f, " {}", self.constraint)?;
+        if self.constraint != JoinConstraint::Empty {
+            write!(f, " {}", self.constraint)?;
+        }
         Ok(())
     }
 }
@@ -76,6 +82,38 @@ impl fmt::Display for LimitClause {
     }
 }
 
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum LockMode {
+    Update,
+    Share,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum LockWait {
+    NoWait,
+    SkipLocked,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct LockClause {
+    pub mode: LockMode,
+    pub wait: Option<LockWait>,
+}
+
+impl fmt::Display for LockClause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            LockMode::Update => write!(f, "FOR UPDATE")?,
+            LockMode::Share => write!(f, "FOR SHARE")?,
+        }
+        match self.wait {
+            Some(LockWait::NoWait) => write!(f, " NOWAIT"),
+            Some(LockWait::SkipLocked) => write!(f, " SKIP LOCKED"),
+            None => Ok(()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct SelectStatement {
     pub tables: Vec<Table>,
@@ -86,6 +124,7 @@ pub struct SelectStatement {
     pub group_by: Option<GroupByClause>,
     pub order: Option<OrderClause>,
     pub limit: Option<LimitClause>,
+    pub lock: Option<LockClause>,
 }
 
 impl fmt::Display for SelectStatement {
@@ -132,6 +171,9 @@ impl fmt::Display for SelectStatement {
         if let Some(ref limit) = self.limit {
             write!(f, " {}", limit)?;
         }
+        if let Some(ref lock) = self.lock {
+            write!(f, " {}", lock)?;
+        }
         Ok(())
     }
 }
@@ -147,17 +189,38 @@ fn having_clause(i: &[u8]) -> IResult<&[u8], ConditionExpression> {
     Ok((remaining_input, ce))
 }
 
+fn with_rollup(i: &[u8]) -> IResult<&[u8], ()> {
+    let (remaining_input, _) = tuple((
+        multispace1,
+        tag_no_case("with"),
+        multispace1,
+        tag_no_case("rollup"),
+    ))(i)?;
+
+    Ok((remaining_input, ()))
+}
+
 // Parse GROUP BY clause
 pub fn group_by_clause(i: &[u8]) -> IResult<&[u8], GroupByClause> {
-    let (remaining_input, (_, _, _, columns, having)) = tuple((
+    let (remaining_input, (_, _, _, _, _, columns, with_rollup, having)) = tuple((
         multispace0,
-        tag_no_case("group by"),
+        tag_no_case("group"),
+        multispace1,
+        tag_no_case("by"),
         multispace1,
         field_list,
+        opt(with_rollup),
         opt(having_clause),
     ))(i)?;
 
-    Ok((remaining_input, GroupByClause { columns, having }))
+    Ok((
+        remaining_input,
+        GroupByClause {
+            columns,
+            having,
+            with_rollup: with_rollup.is_some(),
+        },
+    ))
 }
 
 fn offset(i: &[u8]) -> IResult<&[u8], u64> {
@@ -171,19 +234,23 @@ fn offset(i: &[u8]) -> IResult<&[u8], u64> {
     Ok((remaining_input, val))
 }
 
-// Parse LIMIT clause
+// Parse LIMIT clause. Besides `LIMIT count [OFFSET offset]`, this accepts MySQL's
+// `LIMIT offset, count` form; note that the offset comes *first* there.
 pub fn limit_clause(i: &[u8]) -> IResult<&[u8], LimitClause> {
-    let (remaining_input, (_, _, _, limit, opt_offset)) = tuple((
+    let (remaining_input, (_, _, _, (limit, offset))) = tuple((
         multispace0,
         tag_no_case("limit"),
         multispace1,
-        unsigned_number,
-        opt(offset),
+        alt((
+            map(
+                separated_pair(unsigned_number, ws_sep_comma, unsigned_number),
+                |(offset, limit)| (limit, offset),
+            ),
+            map(tuple((unsigned_number, opt(offset))), |(limit, offset)| {
+                (limit, offset.unwrap_or(0))
+            }),
+        )),
     ))(i)?;
-    let offset = match opt_offset {
-        None => 0,
-        Some(v) => v,
-    };
 
     Ok((remaining_input, LimitClause { limit, offset }))
 }
@@ -192,7 +259,7 @@ fn join_constraint(i: &[u8]) -> IResult<&[u8], JoinConstraint> {
     let using_clause = map(
         tuple((
             tag_no_case("using"),
-            multispace1,
+            multispace0,
             delimited(
                 terminated(tag("("), multispace0),
                 field_list,
@@ -218,15 +285,15 @@ fn join_constraint(i: &[u8]) -> IResult<&[u8], JoinConstraint> {
 
 // Parse JOIN clause
 fn join_clause(i: &[u8]) -> IResult<&[u8], JoinClause> {
-    let (remaining_input, (_, _natural, operator, _, right, _, constraint)) = tuple((
+    let (remaining_input, (_, _natural, operator, _, right, constraint)) = tuple((
         multispace0,
         opt(terminated(tag_no_case("natural"), multispace1)),
         join_operator,
         multispace1,
         join_rhs,
-        multispace1,
-        join_constraint,
+        opt(preceded(multispace1, join_constraint)),
     ))(i)?;
+    let constraint = constraint.unwrap_or(JoinConstraint::Empty);
 
     Ok((
         remaining_input,
@@ -268,6 +335,39 @@ pub fn where_clause(i: &[u8]) -> IResult<&[u8], ConditionExpression> {
     Ok((remaining_input, where_condition))
 }
 
+// Parse the optional DISTINCT or ALL keyword following SELECT; true for DISTINCT.
+fn set_quantifier(i: &[u8]) -> IResult<&[u8], bool> {
+    alt((
+        map(tag_no_case("distinct"), |_| true),
+        map(tag_no_case("all"), |_| false),
+    ))(i)
+}
+
+// Parse rule for a trailing `FOR UPDATE` / `FOR SHARE [NOWAIT | SKIP LOCKED]` locking clause.
+pub fn lock_clause(i: &[u8]) -> IResult<&[u8], LockClause> {
+    let (remaining_input, (_, _, _, mode, wait)) = tuple((
+        multispace0,
+        tag_no_case("for"),
+        multispace1,
+        alt((
+            map(tag_no_case("update"), |_| LockMode::Update),
+            map(tag_no_case("share"), |_| LockMode::Share),
+        )),
+        opt(preceded(
+            multispace1,
+            alt((
+                map(tag_no_case("nowait"), |_| LockWait::NoWait),
+                map(
+                    tuple((tag_no_case("skip"), multispace1, tag_no_case("locked"))),
+                    |_| LockWait::SkipLocked,
+                ),
+            )),
+        )),
+    ))(i)?;
+
+    Ok((remaining_input, LockClause { mode, wait }))
+}
+
 // Parse rule for a SQL selection query.
 pub fn selection(i: &[u8]) -> IResult<&[u8], SelectStatement> {
     terminated(nested_selection, statement_terminator)(i)
@@ -276,12 +376,11 @@ pub fn selection(i: &[u8]) -> IResult<&[u8], SelectStatement> {
 pub fn nested_selection(i: &[u8]) -> IResult<&[u8], SelectStatement> {
     let (
         remaining_input,
-        (_, _, distinct, _, fields, _, tables, join, where_clause, group_by, order, limit),
+        (_, _, distinct, fields, _, tables, join, where_clause, group_by, order, limit, lock),
     ) = tuple((
         tag_no_case("select"),
         multispace1,
-        opt(tag_no_case("distinct")),
-        multispace0,
+        opt(terminated(set_quantifier, multispace1)),
         field_definition_expr,
         delimited(multispace0, tag_no_case("from"), multispace0),
         table_list,
@@ -290,18 +389,20 @@ pub fn nested_selection(i: &[u8]) -> IResult<&[u8], SelectStatement> {
         opt(group_by_clause),
         opt(order_clause),
         opt(limit_clause),
+        opt(lock_clause),
     ))(i)?;
     Ok((
         remaining_input,
         SelectStatement {
             tables,
-            distinct: distinct.is_some(),
+            distinct: distinct.unwrap_or(false),
             fields,
             join,
             where_clause,
             group_by,
             order,
             limit,
+            lock,
         },
     ))
 }
@@ -309,14 +410,17 @@ pub fn nested_selection(i: &[u8]) -> IResult<&[u8], SelectStatement> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use case::{CaseWhenExpression, ColumnOrLiteral};
+    use case::{CaseExpression, ColumnOrLiteral};
+    use cast::CastTarget;
     use column::{Column, FunctionArgument, FunctionArguments, FunctionExpression};
     use common::{
         FieldDefinitionExpression, FieldValueExpression, ItemPlaceholder, Literal, Operator,
+        SqlType,
     };
     use condition::ConditionBase::*;
     use condition::ConditionExpression::*;
     use condition::ConditionTree;
+    use dialect::{with_dialect, Dialect};
     use order::OrderType;
     use table::Table;
 
@@ -521,26 +625,81 @@ mod tests {
         assert_eq!(res2.unwrap().1.limit, Some(expected_lim2));
     }
 
+    #[test]
+    fn limit_clause_comma_form() {
+        // MySQL's `LIMIT offset, count`: the first number is the offset, not the count
+        let qstring1 = "select * from users limit 20, 10\n";
+        let qstring2 = "select * from users limit 10 offset 20\n";
+
+        let expected_lim = LimitClause {
+            limit: 10,
+            offset: 20,
+        };
+
+        let res1 = selection(qstring1.as_bytes()).unwrap().1;
+        let res2 = selection(qstring2.as_bytes()).unwrap().1;
+        assert_eq!(res1.limit, Some(expected_lim));
+        assert_eq!(res1, res2);
+        assert_eq!(
+            format!("{}", res1),
+            "SELECT * FROM users LIMIT 10 OFFSET 20"
+        );
+    }
+
     #[test]
     fn table_alias() {
         let qstring1 = "select * from PaperTag as t;";
-        // let qstring2 = "select * from PaperTag t;";
+        let qstring2 = "select * from PaperTag t;";
+
+        let res1 = selection(qstring1.as_bytes());
+        let expected = SelectStatement {
+            tables: vec![Table {
+                name: String::from("PaperTag"),
+                alias: Some(String::from("t")),
+                schema: None,
+                catalog: None,
+            }],
+            fields: vec![FieldDefinitionExpression::All],
+            ..Default::default()
+        };
+        assert_eq!(res1.unwrap().1, expected);
+        let res2 = selection(qstring2.as_bytes());
+        assert_eq!(res2.unwrap().1, expected);
+    }
+
+    #[test]
+    fn column_and_table_alias() {
+        let qstring1 = "SELECT id AS user_id FROM users AS u;";
+        let qstring2 = "SELECT id FROM users;";
 
         let res1 = selection(qstring1.as_bytes());
         assert_eq!(
             res1.unwrap().1,
             SelectStatement {
                 tables: vec![Table {
-                    name: String::from("PaperTag"),
-                    alias: Some(String::from("t")),
-					schema: None,
-                },],
-                fields: vec![FieldDefinitionExpression::All],
+                    name: String::from("users"),
+                    alias: Some(String::from("u")),
+                    schema: None,
+                    catalog: None,
+                }],
+                fields: vec![FieldDefinitionExpression::Col(Column {
+                    name: String::from("id"),
+                    alias: Some(String::from("user_id")),
+                    table: None,
+                    function: None,
+                })],
+                ..Default::default()
+            }
+        );
+        let res2 = selection(qstring2.as_bytes());
+        assert_eq!(
+            res2.unwrap().1,
+            SelectStatement {
+                tables: vec![Table::from("users")],
+                fields: columns(&["id"]),
                 ..Default::default()
             }
         );
-        // let res2 = selection(qstring2.as_bytes());
-        // assert_eq!(res1.unwrap().1, res2.unwrap().1);
     }
 
     #[test]
@@ -555,6 +714,7 @@ mod tests {
                     name: String::from("PaperTag"),
                     alias: Some(String::from("t")),
 					schema: Some(String::from("db1")),
+                    catalog: None,
                 },],
                 fields: vec![FieldDefinitionExpression::All],
                 ..Default::default()
@@ -564,6 +724,28 @@ mod tests {
         // assert_eq!(res1.unwrap().1, res2.unwrap().1);
     }
 
+    #[test]
+    fn three_part_table_name() {
+        let qstring = "select * from mydb.dbo.users u;";
+
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.tables,
+            vec![Table {
+                name: String::from("users"),
+                alias: Some(String::from("u")),
+                schema: Some(String::from("dbo")),
+                catalog: Some(String::from("mydb")),
+            }]
+        );
+        assert_eq!(format!("{}", res), "SELECT * FROM mydb.dbo.users AS u");
+
+        // MySQL has no catalogs, so a third part is an error
+        let parse = || selection(qstring.as_bytes());
+        assert!(with_dialect(Dialect::PostgreSQL, parse).is_ok());
+        assert!(with_dialect(Dialect::MySQL, parse).is_err());
+    }
+
     #[test]
     fn column_alias() {
         let qstring1 = "select name as TagName from PaperTag;";
@@ -659,6 +841,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn distinct_and_all() {
+        let qstring1 = "SELECT DISTINCT a FROM t;";
+        let qstring2 = "SELECT ALL a FROM t;";
+        let qstring3 = "SELECT a FROM t;";
+        let qstring4 = "SELECT distinctive FROM t;";
+
+        let expected = SelectStatement {
+            tables: vec![Table::from("t")],
+            fields: columns(&["a"]),
+            ..Default::default()
+        };
+        let res1 = selection(qstring1.as_bytes());
+        let res2 = selection(qstring2.as_bytes());
+        let res3 = selection(qstring3.as_bytes());
+        let res4 = selection(qstring4.as_bytes());
+        assert_eq!(
+            res1.unwrap().1,
+            SelectStatement {
+                distinct: true,
+                ..expected.clone()
+            }
+        );
+        assert_eq!(res2.unwrap().1, expected);
+        assert_eq!(res3.unwrap().1, expected);
+        assert_eq!(
+            res4.unwrap().1,
+            SelectStatement {
+                fields: columns(&["distinctive"]),
+                ..expected
+            }
+        );
+    }
+
+    #[test]
+    fn distinct_aggregate() {
+        let qstring = "SELECT DISTINCT COUNT(x) FROM t;";
+
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        assert!(res.distinct);
+        assert_eq!(format!("{}", res), "SELECT DISTINCT count(x) FROM t");
+    }
+
     #[test]
     fn simple_condition_expr() {
         let qstring = "select infoJson from PaperStorage where paperId=? and paperStorageId=?;";
@@ -784,6 +1009,7 @@ mod tests {
             group_by: Some(GroupByClause {
                 columns: vec![Column::from("aid")],
                 having: None,
+                with_rollup: false,
             }),
             ..Default::default()
         };
@@ -808,12 +1034,115 @@ mod tests {
             group_by: Some(GroupByClause {
                 columns: vec![Column::from("aid")],
                 having: None,
+                with_rollup: false,
             }),
             ..Default::default()
         };
         assert_eq!(res.unwrap().1, expected_stmt);
     }
 
+    #[test]
+    fn sum_avg_min_aggregates() {
+        let qstring =
+            "SELECT SUM(amount), AVG( amount ), MIN(amount) AS lo, count( * ) FROM payments;";
+
+        let res = selection(qstring.as_bytes());
+        use FunctionExpression::{Avg, Sum};
+        let amount = || FunctionArgument::Column(Column::from("amount"));
+        let agg_col = |name: &str, alias: Option<&str>, f: FunctionExpression| {
+            FieldDefinitionExpression::Col(Column {
+                name: String::from(name),
+                alias: alias.map(String::from),
+                table: None,
+                function: Some(Box::new(f)),
+            })
+        };
+        let expected_stmt = SelectStatement {
+            tables: vec![Table::from("payments")],
+            fields: vec![
+                agg_col("sum(amount)", None, Sum(amount(), false)),
+                agg_col("avg(amount)", None, Avg(amount(), false)),
+                agg_col("lo", Some("lo"), FunctionExpression::Min(amount())),
+                agg_col("count(*)", None, FunctionExpression::CountStar),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(res.unwrap().1, expected_stmt);
+    }
+
+    #[test]
+    fn group_by_columns() {
+        let qstring1 = "SELECT aid FROM votes GROUP BY aid;";
+        let qstring2 = "SELECT aid, uid FROM votes GROUP  BY aid, votes.uid;";
+
+        let res1 = selection(qstring1.as_bytes());
+        let res2 = selection(qstring2.as_bytes());
+        assert_eq!(
+            res1.unwrap().1.group_by,
+            Some(GroupByClause {
+                columns: vec![Column::from("aid")],
+                having: None,
+                with_rollup: false,
+            })
+        );
+        assert_eq!(
+            res2.unwrap().1.group_by,
+            Some(GroupByClause {
+                columns: vec![Column::from("aid"), Column::from("votes.uid")],
+                having: None,
+                with_rollup: false,
+            })
+        );
+    }
+
+    #[test]
+    fn group_by_with_rollup() {
+        let qstring = "SELECT aid, uid, SUM(n) FROM votes GROUP BY aid, uid WITH ROLLUP;";
+
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.group_by,
+            Some(GroupByClause {
+                columns: vec![Column::from("aid"), Column::from("uid")],
+                having: None,
+                with_rollup: true,
+            })
+        );
+        assert_eq!(
+            format!("{}", res),
+            "SELECT aid, uid, sum(n) FROM votes GROUP BY aid, uid WITH ROLLUP"
+        );
+    }
+
+    #[test]
+    fn group_by_having() {
+        let qstring = "SELECT user_id, COUNT(*) FROM orders GROUP BY user_id HAVING COUNT(*) > 5;";
+
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        let having_cond = ComparisonOp(ConditionTree {
+            left: Box::new(Base(Field(Column {
+                name: String::from("count(*)"),
+                alias: None,
+                table: None,
+                function: Some(Box::new(FunctionExpression::CountStar)),
+            }))),
+            right: Box::new(Base(Literal(Literal::Integer(5.into())))),
+            operator: Operator::Greater,
+        });
+        assert_eq!(
+            res.group_by,
+            Some(GroupByClause {
+                columns: vec![Column::from("user_id")],
+                having: Some(having_cond),
+                with_rollup: false,
+            })
+        );
+        assert_eq!(
+            format!("{}", res),
+            "SELECT user_id, count(*) FROM orders GROUP BY user_id HAVING count(*) > 5"
+        );
+    }
+
     #[test]
     fn count_filter() {
         let qstring =
@@ -826,10 +1155,14 @@ mod tests {
             operator: Operator::Greater,
         });
         let agg_expr = FunctionExpression::Count(
-            FunctionArgument::Conditional(CaseWhenExpression {
-                then_expr: ColumnOrLiteral::Column(Column::from("vote_id")),
+            FunctionArgument::Conditional(CaseExpression {
+                operand: None,
+                branches: vec![(
+                    filter_cond,
+                    ColumnOrLiteral::Column(Column::from("vote_id")),
+                )],
                 else_expr: None,
-                condition: filter_cond,
+                alias: None,
             }),
             false,
         );
@@ -844,6 +1177,7 @@ mod tests {
             group_by: Some(GroupByClause {
                 columns: vec![Column::from("aid")],
                 having: None,
+                with_rollup: false,
             }),
             ..Default::default()
         };
@@ -862,10 +1196,14 @@ mod tests {
             operator: Operator::Equal,
         });
         let agg_expr = FunctionExpression::Sum(
-            FunctionArgument::Conditional(CaseWhenExpression {
-                then_expr: ColumnOrLiteral::Column(Column::from("vote_id")),
+            FunctionArgument::Conditional(CaseExpression {
+                operand: None,
+                branches: vec![(
+                    filter_cond,
+                    ColumnOrLiteral::Column(Column::from("vote_id")),
+                )],
                 else_expr: None,
-                condition: filter_cond,
+                alias: None,
             }),
             false,
         );
@@ -880,6 +1218,7 @@ mod tests {
             group_by: Some(GroupByClause {
                 columns: vec![Column::from("aid")],
                 having: None,
+                with_rollup: false,
             }),
             ..Default::default()
         };
@@ -899,10 +1238,14 @@ mod tests {
             operator: Operator::Equal,
         });
         let agg_expr = FunctionExpression::Sum(
-            FunctionArgument::Conditional(CaseWhenExpression {
-                then_expr: ColumnOrLiteral::Column(Column::from("vote_id")),
+            FunctionArgument::Conditional(CaseExpression {
+                operand: None,
+                branches: vec![(
+                    filter_cond,
+                    ColumnOrLiteral::Column(Column::from("vote_id")),
+                )],
                 else_expr: Some(ColumnOrLiteral::Literal(Literal::Integer(6))),
-                condition: filter_cond,
+                alias: None,
             }),
             false,
         );
@@ -917,12 +1260,75 @@ mod tests {
             group_by: Some(GroupByClause {
                 columns: vec![Column::from("aid")],
                 having: None,
+                with_rollup: false,
             }),
             ..Default::default()
         };
         assert_eq!(res.unwrap().1, expected_stmt);
     }
 
+    #[test]
+    fn sum_filter_multiple_branches() {
+        let qstring = "SELECT SUM(CASE WHEN a = 1 THEN b WHEN a = 2 THEN c END) FROM t";
+        let res = selection(qstring.as_bytes()).unwrap().1;
+
+        let when = |value: i64| {
+            ComparisonOp(ConditionTree {
+                left: Box::new(Base(Field(Column::from("a")))),
+                right: Box::new(Base(Literal(Literal::Integer(value)))),
+                operator: Operator::Equal,
+            })
+        };
+        let agg_expr = FunctionExpression::Sum(
+            FunctionArgument::Conditional(CaseExpression {
+                operand: None,
+                branches: vec![
+                    (when(1), ColumnOrLiteral::Column(Column::from("b"))),
+                    (when(2), ColumnOrLiteral::Column(Column::from("c"))),
+                ],
+                else_expr: None,
+                alias: None,
+            }),
+            false,
+        );
+        assert_eq!(
+            res.fields,
+            vec![FieldDefinitionExpression::Col(Column {
+                name: format!("{}", agg_expr),
+                alias: None,
+                table: None,
+                function: Some(Box::new(agg_expr)),
+            })]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "SELECT sum(CASE WHEN a = 1 THEN b WHEN a = 2 THEN c END) FROM t"
+        );
+    }
+
+    #[test]
+    fn case_in_arithmetic() {
+        use arithmetic::{ArithmeticBase, ArithmeticItem};
+
+        let qstring = "SELECT CASE WHEN a = 1 THEN b ELSE 0 END + 1 AS x FROM t";
+        let res = selection(qstring.as_bytes()).unwrap().1;
+
+        match res.fields[0] {
+            FieldDefinitionExpression::Value(FieldValueExpression::Arithmetic(ref expr)) => {
+                assert_eq!(expr.alias, Some(String::from("x")));
+                match expr.ari.left {
+                    ArithmeticItem::Base(ArithmeticBase::Case(ref case)) => {
+                        assert_eq!(case.branches.len(), 1);
+                        assert_eq!(case.alias, None);
+                    }
+                    ref l => panic!("expected a CASE operand, got {:?}", l),
+                }
+            }
+            ref f => panic!("expected an arithmetic field, got {:?}", f),
+        }
+        assert_eq!(format!("{}", res), qstring);
+    }
+
     #[test]
     fn count_filter_lobsters() {
         let qstring = "SELECT
@@ -936,7 +1342,7 @@ mod tests {
             left: Box::new(ComparisonOp(ConditionTree {
                 left: Box::new(Base(Field(Column::from("votes.story_id")))),
                 right: Box::new(Base(Literal(Literal::Null))),
-                operator: Operator::Equal,
+                operator: Operator::Is,
             })),
             right: Box::new(ComparisonOp(ConditionTree {
                 left: Box::new(Base(Field(Column::from("votes.vote")))),
@@ -946,10 +1352,14 @@ mod tests {
             operator: Operator::And,
         });
         let agg_expr = FunctionExpression::Count(
-            FunctionArgument::Conditional(CaseWhenExpression {
-                then_expr: ColumnOrLiteral::Column(Column::from("votes.vote")),
+            FunctionArgument::Conditional(CaseExpression {
+                operand: None,
+                branches: vec![(
+                    filter_cond,
+                    ColumnOrLiteral::Column(Column::from("votes.vote")),
+                )],
                 else_expr: None,
-                condition: filter_cond,
+                alias: None,
             }),
             false,
         );
@@ -964,12 +1374,68 @@ mod tests {
             group_by: Some(GroupByClause {
                 columns: vec![Column::from("votes.comment_id")],
                 having: None,
+                with_rollup: false,
             }),
             ..Default::default()
         };
         assert_eq!(res.unwrap().1, expected_stmt);
     }
 
+    #[test]
+    fn replace_function_is_not_a_statement() {
+        let qstring = "SELECT replace(a, b, c) FROM t;";
+
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        let expected = FunctionExpression::Generic(
+            String::from("replace"),
+            FunctionArguments::from(vec![
+                FunctionArgument::Column("a".into()),
+                FunctionArgument::Column("b".into()),
+                FunctionArgument::Column("c".into()),
+            ]),
+        );
+        match res.fields[0] {
+            FieldDefinitionExpression::Col(ref col) => {
+                assert_eq!(col.function, Some(Box::new(expected)))
+            }
+            ref other => panic!("unexpected field {:?}", other),
+        }
+    }
+
+    #[test]
+    fn scalar_function_calls() {
+        let function = |field: &str| {
+            let qstring = format!("SELECT {} FROM t", field);
+            match selection(qstring.as_bytes()).unwrap().1.fields[0] {
+                FieldDefinitionExpression::Col(ref col) => col.function.clone().map(|f| *f),
+                ref other => panic!("unexpected field {:?}", other),
+            }
+        };
+        let generic = |name: &str, arguments: Vec<FunctionArgument>| {
+            Some(FunctionExpression::Generic(
+                String::from(name),
+                FunctionArguments::from(arguments),
+            ))
+        };
+
+        assert_eq!(function("NOW()"), generic("NOW", vec![]));
+        assert_eq!(
+            function("LOWER(name)"),
+            generic("LOWER", vec![FunctionArgument::Column("name".into())])
+        );
+        assert_eq!(
+            function("COALESCE(a, 'none', 0)"),
+            generic(
+                "COALESCE",
+                vec![
+                    FunctionArgument::Column("a".into()),
+                    FunctionArgument::Literal(Literal::String(String::from("none"))),
+                    FunctionArgument::Literal(Literal::Integer(0)),
+                ]
+            )
+        );
+    }
+
     #[test]
     fn generic_function_query() {
         let qstring = "SELECT coalesce(a, b,c) as x,d FROM sometable;";
@@ -1117,6 +1583,30 @@ mod tests {
         assert_eq!(res.unwrap().1, expected);
     }
 
+    #[test]
+    fn join_using_multiple_columns() {
+        let qstring1 = "SELECT * FROM a JOIN b USING (id, tenant_id);";
+        let qstring2 = "SELECT * FROM a JOIN b USING(`id` ,`tenant_id` );";
+
+        let expected_stmt = SelectStatement {
+            tables: vec![Table::from("a")],
+            fields: vec![FieldDefinitionExpression::All],
+            join: vec![JoinClause {
+                operator: JoinOperator::Join,
+                right: JoinRightSide::Table(Table::from("b")),
+                constraint: JoinConstraint::Using(vec![
+                    Column::from("id"),
+                    Column::from("tenant_id"),
+                ]),
+            }],
+            ..Default::default()
+        };
+        let res1 = selection(qstring1.as_bytes());
+        let res2 = selection(qstring2.as_bytes());
+        assert_eq!(res1.unwrap().1, expected_stmt);
+        assert_eq!(res2.unwrap().1, expected_stmt);
+    }
+
     #[test]
     fn multi_join() {
         // simplified from
@@ -1373,6 +1863,173 @@ mod tests {
         assert_eq!(res.unwrap().1, expected);
     }
 
+    #[test]
+    fn case_in_fields_and_where() {
+        let qstring = "SELECT id, CASE WHEN a > 1 THEN 'hi' ELSE 'lo' END AS size FROM t \
+                       WHERE CASE x WHEN 1 THEN y END = 1";
+        let res = selection(qstring.as_bytes()).unwrap().1;
+
+        match res.fields[1] {
+            FieldDefinitionExpression::Value(FieldValueExpression::Case(ref case)) => {
+                assert_eq!(case.operand, None);
+                assert_eq!(case.alias, Some(String::from("size")));
+            }
+            ref f => panic!("expected a CASE field, got {:?}", f),
+        }
+        match res.where_clause {
+            Some(ComparisonOp(ref tree)) => match *tree.left {
+                Case(ref case) => {
+                    assert_eq!(
+                        case.operand,
+                        Some(ColumnOrLiteral::Column(Column::from("x")))
+                    );
+                    assert_eq!(case.else_expr, None);
+                }
+                ref e => panic!("expected a CASE condition, got {:?}", e),
+            },
+            ref w => panic!("unexpected where clause {:?}", w),
+        }
+        assert_eq!(
+            format!("{}", res),
+            "SELECT id, CASE WHEN a > 1 THEN 'hi' ELSE 'lo' END AS size FROM t \
+             WHERE CASE x WHEN 1 THEN y END = 1"
+        );
+    }
+
+    #[test]
+    fn cast_shorthand_with_alias() {
+        let qstring = "SELECT price::int AS p, price::double AS q, id::text r FROM t";
+        let res = selection(qstring.as_bytes()).unwrap().1;
+
+        let casts: Vec<_> = res
+            .fields
+            .iter()
+            .map(|f| match *f {
+                FieldDefinitionExpression::Value(FieldValueExpression::Cast(ref cast)) => {
+                    (cast.target.clone(), cast.alias.clone().unwrap())
+                }
+                ref f => panic!("expected a CAST field, got {:?}", f),
+            })
+            .collect();
+        assert_eq!(
+            casts,
+            vec![
+                (CastTarget::Type(SqlType::Int(None)), String::from("p")),
+                (CastTarget::Type(SqlType::Double), String::from("q")),
+                (CastTarget::Type(SqlType::Text), String::from("r")),
+            ]
+        );
+        assert_eq!(
+            format!("{}", res),
+            "SELECT CAST(price AS INT) AS p, CAST(price AS DOUBLE) AS q, \
+             CAST(id AS TEXT) AS r FROM t"
+        );
+    }
+
+    #[test]
+    fn cast_in_fields_and_where() {
+        let qstring = "SELECT CAST(id AS CHAR) AS id_str FROM t WHERE CAST(x AS UNSIGNED) = 3";
+        let res = selection(qstring.as_bytes()).unwrap().1;
+
+        match res.fields[0] {
+            FieldDefinitionExpression::Value(FieldValueExpression::Cast(ref cast)) => {
+                assert_eq!(cast.target, CastTarget::Char);
+                assert_eq!(cast.alias, Some(String::from("id_str")));
+            }
+            ref f => panic!("expected a CAST field, got {:?}", f),
+        }
+        match res.where_clause {
+            Some(ComparisonOp(ref tree)) => match *tree.left {
+                Cast(ref cast) => assert_eq!(cast.target, CastTarget::Unsigned),
+                ref e => panic!("expected a CAST condition, got {:?}", e),
+            },
+            ref w => panic!("unexpected where clause {:?}", w),
+        }
+        assert_eq!(format!("{}", res), qstring);
+    }
+
+    #[test]
+    fn project_arithmetic_precedence() {
+        use arithmetic::{
+            Arithmetic, ArithmeticBase, ArithmeticExpression, ArithmeticItem, ArithmeticOperator,
+        };
+
+        let col = |name: &str| ArithmeticBase::Column(name.into());
+
+        let qstr = "SELECT a + b * c AS total, (a + b) * c FROM t;";
+        let res = selection(qstr.as_bytes());
+
+        let expected = SelectStatement {
+            tables: vec![Table::from("t")],
+            fields: vec![
+                FieldDefinitionExpression::Value(FieldValueExpression::Arithmetic(
+                    ArithmeticExpression {
+                        ari: Arithmetic {
+                            op: ArithmeticOperator::Add,
+                            left: ArithmeticItem::Base(col("a")),
+                            right: ArithmeticItem::Expr(Box::new(Arithmetic::new(
+                                ArithmeticOperator::Multiply,
+                                col("b"),
+                                col("c"),
+                            ))),
+                        },
+                        alias: Some(String::from("total")),
+                    },
+                )),
+                FieldDefinitionExpression::Value(FieldValueExpression::Arithmetic(
+                    ArithmeticExpression::new(
+                        ArithmeticOperator::Multiply,
+                        ArithmeticBase::Bracketed(Box::new(Arithmetic::new(
+                            ArithmeticOperator::Add,
+                            col("a"),
+                            col("b"),
+                        ))),
+                        col("c"),
+                        None,
+                    ),
+                )),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(res.unwrap().1, expected);
+    }
+
+    #[test]
+    fn for_update_and_for_share() {
+        let qstring = "SELECT id FROM users WHERE id = 1 FOR UPDATE;";
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.lock,
+            Some(LockClause {
+                mode: LockMode::Update,
+                wait: None,
+            })
+        );
+        assert_eq!(
+            format!("{}", res),
+            "SELECT id FROM users WHERE id = 1 FOR UPDATE"
+        );
+
+        let qstring = "select id from users limit 10 for share nowait";
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        assert_eq!(
+            res.lock,
+            Some(LockClause {
+                mode: LockMode::Share,
+                wait: Some(LockWait::NoWait),
+            })
+        );
+        assert_eq!(
+            format!("{}", res),
+            "SELECT id FROM users LIMIT 10 FOR SHARE NOWAIT"
+        );
+
+        let qstring = "SELECT id FROM jobs FOR UPDATE SKIP LOCKED";
+        let res = selection(qstring.as_bytes()).unwrap().1;
+        assert_eq!(res.lock.unwrap().wait, Some(LockWait::SkipLocked));
+    }
+
     #[test]
     fn where_in_clause() {
         let qstr = "SELECT `auth_permission`.`content_type_id`, `auth_permission`.`codename`
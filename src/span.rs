@@ -0,0 +1,41 @@
+use std::ops::Range;
+
+use context;
+
+/// The byte offsets of a parsed construct in the input, from its first byte up to (but not
+/// including) the byte following it.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Range<usize> {
+        span.start..span.end
+    }
+}
+
+/// Runs `f` while recording the spans of constructs parsed from `input`, which must contain
+/// everything `f` parses.
+pub(crate) fn with_spans<T, F: FnOnce() -> T>(input: &[u8], f: F) -> T {
+    let start = Some(input.as_ptr() as usize);
+    context::scoped(|c| &mut c.span_start, start, f).0
+}
+
+/// Returns the span of the text a parser consumed from `before`, leaving `after`, without
+/// trailing whitespace; `None` unless spans are being recorded.
+pub(crate) fn span(before: &[u8], after: &[u8]) -> Option<Span> {
+    context::with(|c| c.span_start).map(|start| {
+        let consumed = &before[..before.len() - after.len()];
+        let len = consumed
+            .iter()
+            .rposition(|c| !c.is_ascii_whitespace())
+            .map_or(0, |last| last + 1);
+        let offset = (before.as_ptr() as usize).saturating_sub(start);
+        Span {
+            start: offset,
+            end: offset + len,
+        }
+    })
+}
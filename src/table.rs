@@ -8,10 +8,34 @@ pub struct Table {
     pub name: String,
     pub alias: Option<String>,
     pub schema: Option<String>,
+    /// The database (catalog) of a three-part `catalog.schema.table` name.
+    pub catalog: Option<String>,
+}
+
+impl Table {
+    /// Compares two tables ignoring ASCII case in every name part, as SQL identifiers usually
+    /// are. The derived `PartialEq` remains case-sensitive.
+    pub fn eq_ignore_case(&self, other: &Table) -> bool {
+        self.name.eq_ignore_ascii_case(&other.name)
+            && opt_eq_ignore_case(&self.alias, &other.alias)
+            && opt_eq_ignore_case(&self.schema, &other.schema)
+            && opt_eq_ignore_case(&self.catalog, &other.catalog)
+    }
+}
+
+pub(crate) fn opt_eq_ignore_case(a: &Option<String>, b: &Option<String>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (None, None) => true,
+        _ => false,
+    }
 }
 
 impl fmt::Display for Table {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref catalog) = self.catalog {
+            write!(f, "{}.", escape_if_keyword(catalog))?;
+        }
         if let Some(ref schema) = self.schema {
             write!(f, "{}.", escape_if_keyword(schema))?;
         }
@@ -23,12 +47,18 @@ impl fmt::Display for Table {
     }
 }
 
+/// Splits a dotted `schema.name` or `catalog.schema.name` into its parts.
 impl<'a> From<&'a str> for Table {
     fn from(t: &str) -> Table {
+        let mut parts = t.rsplitn(3, '.');
+        let name = parts.next().unwrap();
+        let schema = parts.next();
+        let catalog = parts.next();
         Table {
-            name: String::from(t),
+            name: String::from(name),
             alias: None,
-            schema: None,
+            schema: schema.map(String::from),
+            catalog: catalog.map(String::from),
         }
     }
 }
@@ -38,6 +68,46 @@ impl<'a> From<(&'a str, &'a str)> for Table {
             name: String::from(t.1),
             alias: None,
             schema: Some(String::from(t.0)),
+            catalog: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_eq_ignore_case() {
+        let lower = Table::from(("app", "users"));
+        let upper = Table::from(("APP", "Users"));
+        assert_ne!(lower, upper);
+        assert!(lower.eq_ignore_case(&upper));
+
+        assert!(!lower.eq_ignore_case(&Table::from("users")));
+        assert!(!lower.eq_ignore_case(&Table::from(("app", "user"))));
+    }
+
+    #[test]
+    fn table_from_str() {
+        assert_eq!(
+            Table::from("users"),
+            Table {
+                name: String::from("users"),
+                alias: None,
+                schema: None,
+                catalog: None,
+            }
+        );
+        assert_eq!(Table::from("db.users"), Table::from(("db", "users")));
+        assert_eq!(
+            Table::from("def.db.users"),
+            Table {
+                name: String::from("users"),
+                alias: None,
+                schema: Some(String::from("db")),
+                catalog: Some(String::from("def")),
+            }
+        );
+    }
+}
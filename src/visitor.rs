@@ -0,0 +1,163 @@
+use column::{Column, ColumnConstraint, ColumnSpecification};
+use common::{ForeignKeyReference, SqlType, TableKey};
+use create::CreateTableStatement;
+use table::Table;
+
+/// A traversal over the AST. Every method does nothing by default, except for those visiting
+/// nodes with children, which walk into them through the matching `walk_*` function; override a
+/// method and call that function to keep descending.
+pub trait Visitor {
+    fn visit_create_table(&mut self, create: &CreateTableStatement) {
+        walk_create_table(self, create)
+    }
+
+    fn visit_table(&mut self, _table: &Table) {}
+
+    fn visit_column(&mut self, _column: &Column) {}
+
+    fn visit_sql_type(&mut self, _sql_type: &SqlType) {}
+
+    fn visit_column_specification(&mut self, spec: &ColumnSpecification) {
+        walk_column_specification(self, spec)
+    }
+
+    fn visit_column_constraint(&mut self, constraint: &ColumnConstraint) {
+        walk_column_constraint(self, constraint)
+    }
+
+    fn visit_table_key(&mut self, key: &TableKey) {
+        walk_table_key(self, key)
+    }
+
+    fn visit_foreign_key_reference(&mut self, reference: &ForeignKeyReference) {
+        walk_foreign_key_reference(self, reference)
+    }
+}
+
+/// Visits the table, then its column specifications and keys, in declaration order. Keys that
+/// only mirror an inline `PRIMARY KEY` or `REFERENCES` constraint are skipped, as that
+/// constraint is visited with its column.
+pub fn walk_create_table<V: Visitor + ?Sized>(visitor: &mut V, create: &CreateTableStatement) {
+    visitor.visit_table(&create.table);
+    for spec in &create.fields {
+        visitor.visit_column_specification(spec);
+    }
+    for key in create.keys.iter().flatten() {
+        if !create.mirrors_inline_constraint(key) {
+            visitor.visit_table_key(key);
+        }
+    }
+}
+
+/// Visits the column, its type and then its constraints.
+pub fn walk_column_specification<V: Visitor + ?Sized>(visitor: &mut V, spec: &ColumnSpecification) {
+    visitor.visit_column(&spec.column);
+    visitor.visit_sql_type(&spec.sql_type);
+    for constraint in &spec.constraints {
+        visitor.visit_column_constraint(constraint);
+    }
+}
+
+/// Visits the reference of a `REFERENCES` constraint; other constraints have no children.
+pub fn walk_column_constraint<V: Visitor + ?Sized>(visitor: &mut V, constraint: &ColumnConstraint) {
+    if let ColumnConstraint::References(ref reference) = *constraint {
+        visitor.visit_foreign_key_reference(reference);
+    }
+}
+
+/// Visits the key's columns, and then the reference of a foreign key.
+pub fn walk_table_key<V: Visitor + ?Sized>(visitor: &mut V, key: &TableKey) {
+    match *key {
+        TableKey::PrimaryKey { ref columns, .. }
+        | TableKey::UniqueKey { ref columns, .. }
+        | TableKey::FulltextKey { ref columns, .. }
+        | TableKey::Key { ref columns, .. } => {
+            for column in columns {
+                visitor.visit_column(&column.column);
+            }
+        }
+        TableKey::ForeignKey {
+            ref columns,
+            ref reference,
+            ..
+        } => {
+            for column in columns {
+                visitor.visit_column(column);
+            }
+            visitor.visit_foreign_key_reference(reference);
+        }
+    }
+}
+
+/// Visits the referenced table, then the referenced columns.
+pub fn walk_foreign_key_reference<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    reference: &ForeignKeyReference,
+) {
+    visitor.visit_table(&reference.table);
+    for column in &reference.columns {
+        visitor.visit_column(column);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use create::creation;
+
+    #[test]
+    fn count_columns() {
+        struct ColumnCounter(usize);
+
+        impl Visitor for ColumnCounter {
+            fn visit_column_specification(&mut self, _spec: &ColumnSpecification) {
+                self.0 += 1;
+            }
+        }
+
+        let qstring =
+            "CREATE TABLE t (id int PRIMARY KEY, name varchar(10), age int, KEY n (name))";
+        let create = creation(qstring.as_bytes()).unwrap().1;
+        let mut counter = ColumnCounter(0);
+        counter.visit_create_table(&create);
+        assert_eq!(counter.0, 3);
+    }
+
+    #[test]
+    fn collect_table_names() {
+        struct TableNames(Vec<String>);
+
+        impl Visitor for TableNames {
+            fn visit_table(&mut self, table: &Table) {
+                if !self.0.contains(&table.name) {
+                    self.0.push(table.name.clone());
+                }
+            }
+        }
+
+        let qstring = "CREATE TABLE posts (id int, author int REFERENCES users (id), \
+                       parent int, FOREIGN KEY (parent) REFERENCES posts (id))";
+        let create = creation(qstring.as_bytes()).unwrap().1;
+        let mut names = TableNames(vec![]);
+        walk_create_table(&mut names, &create);
+        assert_eq!(names.0, vec!["posts", "users"]);
+    }
+
+    #[test]
+    fn inline_references_visited_once() {
+        struct References(Vec<String>);
+
+        impl Visitor for References {
+            fn visit_foreign_key_reference(&mut self, reference: &ForeignKeyReference) {
+                self.0.push(reference.table.name.clone());
+            }
+        }
+
+        let qstring = "CREATE TABLE posts (id int PRIMARY KEY, author int REFERENCES users (id), \
+                       parent int, FOREIGN KEY (parent) REFERENCES posts (id))";
+        let create = creation(qstring.as_bytes()).unwrap().1;
+        let mut references = References(vec![]);
+        references.visit_create_table(&create);
+        assert_eq!(references.0, vec!["users", "posts"]);
+    }
+}
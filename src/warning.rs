@@ -0,0 +1,97 @@
+use std::fmt;
+
+use context;
+use nom::IResult;
+
+/// A construct that was recognized while parsing, but is not represented in the parsed query.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Warning {
+    /// Byte offset of the construct in the parsed input.
+    pub offset: usize,
+    /// Description of what was discarded.
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (at byte {})", self.message, self.offset)
+    }
+}
+
+/// Runs `f` while collecting the warnings raised for `input`, which must contain everything `f`
+/// parses. Warnings are returned in input order; ones raised more than once because the parser
+/// backtracked are only reported once.
+pub(crate) fn collect_warnings<T, F: FnOnce() -> T>(input: &[u8], f: F) -> (T, Vec<Warning>) {
+    let collector = Some((input.as_ptr() as usize, vec![]));
+    let (result, collector) = context::scoped(|c| &mut c.warnings, collector, f);
+    let mut warnings = collector.map(|(_, warnings)| warnings).unwrap_or_default();
+    warnings.sort();
+    warnings.dedup();
+    (result, warnings)
+}
+
+/// Records a warning for the construct starting at `at`, if warnings are being collected.
+pub(crate) fn warn<F: FnOnce() -> String>(at: &[u8], message: F) {
+    context::with(|c| {
+        if let Some((start, ref mut warnings)) = c.warnings {
+            warnings.push(Warning {
+                offset: (at.as_ptr() as usize).saturating_sub(start),
+                message: message(),
+            });
+        }
+    })
+}
+
+/// Runs `parser`, keeping the warnings it raises only if it succeeds, so that an `alt` reports
+/// the warnings of the branch it settles on and none from the branches it backtracked out of.
+pub(crate) fn discard_on_failure<'a, O, P>(parser: P) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], O>
+where
+    P: Fn(&'a [u8]) -> IResult<&'a [u8], O>,
+{
+    move |i| {
+        let saved = context::with(|c| c.warnings.as_ref().map(|(_, w)| w.len()));
+        let result = parser(i);
+        if let (Err(_), Some(len)) = (&result, saved) {
+            context::with(|c| {
+                if let Some((_, ref mut warnings)) = c.warnings {
+                    warnings.truncate(len);
+                }
+            });
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+
+    #[test]
+    fn warnings_from_failed_branches_are_discarded() {
+        let warns_then_fails = |i| {
+            warn(i, || "discarded".to_string());
+            tag("ab")(i)
+        };
+        let warns = |i| {
+            warn(i, || "kept".to_string());
+            tag("a")(i)
+        };
+        let input = b"ac";
+        let (result, warnings) = collect_warnings(input, || {
+            alt((
+                discard_on_failure(warns_then_fails),
+                discard_on_failure(warns),
+            ))(input)
+        });
+        assert_eq!(result, Ok((&b"c"[..], &b"a"[..])));
+        assert_eq!(
+            warnings,
+            vec![Warning {
+                offset: 0,
+                message: "kept".to_string(),
+            }]
+        );
+    }
+}
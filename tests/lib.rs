@@ -216,3 +216,17 @@ fn parse_select() {
     assert_eq!(fail, 0);
     assert_eq!(ok, 27);
 }
+
+#[test]
+fn parse_result_outlives_input() {
+    let query = {
+        let buffer = String::from("SELECT users.name FROM users WHERE users.id = 1");
+        nom_sql::parser::parse_query_bytes(buffer.into_bytes()).unwrap()
+    };
+
+    // The AST owns all of its strings, so it stays usable after the input buffer is dropped.
+    assert_eq!(
+        format!("{}", query),
+        "SELECT users.name FROM users WHERE users.id = 1"
+    );
+}